@@ -0,0 +1,272 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![doc = include_str!("../README.md")]
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufWriter;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use fastrace::collector::Reporter;
+use fastrace::collector::Value;
+use fastrace::prelude::*;
+use serde::Serialize;
+use serde::ser::SerializeMap;
+
+const HEADER: &[u8] = b"[\n";
+const SEPARATOR: &[u8] = b",\n";
+const FOOTER: &[u8] = b"\n]\n";
+
+/// [Chrome Trace Event Format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+/// reporter for `fastrace`, writing JSON files that can be opened in
+/// [Perfetto UI](https://ui.perfetto.dev/) or `chrome://tracing`.
+///
+/// Spans are written as complete (`"X"`) events, and their events as instant (`"i"`) events,
+/// with the properties as `args`. Spans are shown on the track of the thread they ended on,
+/// which is named after the thread if it has a name, or on a track per trace if the thread is
+/// unknown.
+///
+/// Files are kept valid JSON arrays after each report, and the events of later reports are
+/// appended to them.
+pub struct ChromeReporter {
+    dir: PathBuf,
+    rotation: Rotation,
+    current: Option<RollingFile>,
+    pid: u32,
+}
+
+enum Rotation {
+    PerTrace,
+    Rolling { max_events: usize },
+}
+
+struct RollingFile {
+    path: PathBuf,
+    events: usize,
+}
+
+impl ChromeReporter {
+    /// Creates a reporter that writes each trace into `{dir}/{trace_id}.json`.
+    pub fn per_trace(dir: impl Into<PathBuf>) -> ChromeReporter {
+        ChromeReporter {
+            dir: dir.into(),
+            rotation: Rotation::PerTrace,
+            current: None,
+            pid: std::process::id(),
+        }
+    }
+
+    /// Creates a reporter that writes all the traces into `{dir}/fastrace-{timestamp}.json`,
+    /// starting a new file once the current one holds `max_events_per_file` events.
+    ///
+    /// The events of a single report are never split across files, so a file may exceed the
+    /// limit when a large batch of spans is reported at once.
+    pub fn rolling(dir: impl Into<PathBuf>, max_events_per_file: usize) -> ChromeReporter {
+        ChromeReporter {
+            dir: dir.into(),
+            rotation: Rotation::Rolling {
+                max_events: max_events_per_file,
+            },
+            current: None,
+            pid: std::process::id(),
+        }
+    }
+
+    fn convert<'a>(&self, spans: impl IntoIterator<Item = &'a SpanRecord>) -> Vec<ChromeEvent<'a>> {
+        let mut events = Vec::new();
+        let mut named_threads = HashSet::new();
+        for s in spans {
+            let tid = s.thread_id.unwrap_or(s.trace_id.0 as u32 as u64);
+            if let Some(thread_name) = &s.thread_name {
+                if named_threads.insert(tid) {
+                    events.push(ChromeEvent {
+                        name: "thread_name",
+                        ph: "M",
+                        ts: 0.0,
+                        dur: None,
+                        scope: None,
+                        pid: self.pid,
+                        tid,
+                        args: Args::ThreadName(thread_name),
+                    });
+                }
+            }
+            events.push(ChromeEvent {
+                name: &s.name,
+                ph: "X",
+                ts: micros(s.begin_time_unix_ns),
+                dur: Some(micros(s.duration_ns)),
+                scope: None,
+                pid: self.pid,
+                tid,
+                args: Args::Properties(&s.properties),
+            });
+            events.extend(s.events.iter().map(|event| ChromeEvent {
+                name: &event.name,
+                ph: "i",
+                ts: micros(event.timestamp_unix_ns),
+                dur: None,
+                scope: Some("t"),
+                pid: self.pid,
+                tid,
+                args: Args::Properties(&event.properties),
+            }));
+        }
+        events
+    }
+
+    fn try_report(&mut self, spans: Vec<SpanRecord>) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        match self.rotation {
+            Rotation::PerTrace => {
+                let mut traces: HashMap<TraceId, Vec<&SpanRecord>> = HashMap::new();
+                for span in &spans {
+                    traces.entry(span.trace_id).or_default().push(span);
+                }
+                for (trace_id, spans) in traces {
+                    let path = self.dir.join(format!("{trace_id}.json"));
+                    append_events(&path, &self.convert(spans))?;
+                }
+            }
+            Rotation::Rolling { max_events } => {
+                let events = self.convert(&spans);
+                let current = match self.current.take() {
+                    Some(current) if current.events < max_events => current,
+                    _ => RollingFile {
+                        path: self.dir.join(format!("fastrace-{}.json", unix_micros())),
+                        events: 0,
+                    },
+                };
+                append_events(&current.path, &events)?;
+                self.current = Some(RollingFile {
+                    events: current.events + events.len(),
+                    ..current
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Reporter for ChromeReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        if let Err(err) = self.try_report(spans) {
+            log::error!("report to chrome trace file failed: {err}");
+        }
+    }
+}
+
+/// Appends `events` to the JSON array in `path`, by overwriting its closing bracket.
+fn append_events(path: &Path, events: &[ChromeEvent]) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)?;
+    let len = file.metadata()?.len();
+    let mut writer = if len >= (HEADER.len() + FOOTER.len()) as u64 {
+        file.seek(SeekFrom::Start(len - FOOTER.len() as u64))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(SEPARATOR)?;
+        writer
+    } else {
+        file.set_len(0)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(HEADER)?;
+        writer
+    };
+
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(SEPARATOR)?;
+        }
+        serde_json::to_writer(&mut writer, event)?;
+    }
+    writer.write_all(FOOTER)?;
+    writer.flush()
+}
+
+fn micros(ns: u64) -> f64 {
+    ns as f64 / 1_000.0
+}
+
+fn unix_micros() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros()
+}
+
+#[derive(Serialize)]
+struct ChromeEvent<'a> {
+    name: &'a str,
+    ph: &'static str,
+    ts: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<f64>,
+    #[serde(rename = "s", skip_serializing_if = "Option::is_none")]
+    scope: Option<&'static str>,
+    pid: u32,
+    tid: u64,
+    #[serde(skip_serializing_if = "Args::is_empty")]
+    args: Args<'a>,
+}
+
+enum Args<'a> {
+    Properties(&'a [(Cow<'static, str>, Value)]),
+    ThreadName(&'a str),
+}
+
+impl Args<'_> {
+    fn is_empty(&self) -> bool {
+        match self {
+            Args::Properties(properties) => properties.is_empty(),
+            Args::ThreadName(_) => false,
+        }
+    }
+}
+
+impl Serialize for Args<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Args::Properties(properties) => {
+                let mut map = serializer.serialize_map(Some(properties.len()))?;
+                for (k, v) in *properties {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
+            Args::ThreadName(name) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("name", name)?;
+                map.end()
+            }
+        }
+    }
+}
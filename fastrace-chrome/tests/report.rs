@@ -0,0 +1,163 @@
+use std::path::Path;
+
+use fastrace::collector::EventRecord;
+use fastrace::collector::Reporter;
+use fastrace::prelude::*;
+use fastrace_chrome::ChromeReporter;
+use serde_json::Value;
+use serde_json::json;
+
+fn span(trace_id: u128, span_id: u64, name: &'static str) -> SpanRecord {
+    SpanRecord {
+        trace_id: TraceId(trace_id),
+        span_id: SpanId(span_id),
+        begin_time_unix_ns: 1_000_000 * span_id,
+        duration_ns: 2_500,
+        name: name.into(),
+        ..SpanRecord::default()
+    }
+}
+
+fn read(path: &Path) -> Vec<Value> {
+    serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+}
+
+fn files(dir: &Path) -> Vec<String> {
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn per_trace_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = ChromeReporter::per_trace(dir.path());
+
+    reporter.report(vec![
+        SpanRecord {
+            properties: vec![
+                ("http.method".into(), "GET".into()),
+                ("http.status_code".into(), 200i64.into()),
+                ("cached".into(), false.into()),
+            ],
+            events: vec![EventRecord {
+                name: "retry".into(),
+                timestamp_unix_ns: 1_001_500,
+                properties: vec![("attempt".into(), 2i64.into())],
+            }],
+            ..span(1, 1, "root")
+        },
+        span(2, 2, "other"),
+    ]);
+    reporter.report(vec![span(1, 3, "late")]);
+
+    assert_eq!(files(dir.path()), vec![
+        "00000000000000000000000000000001.json",
+        "00000000000000000000000000000002.json",
+    ]);
+
+    let pid = std::process::id();
+    assert_eq!(
+        read(&dir.path().join("00000000000000000000000000000001.json")),
+        vec![
+            json!({
+                "name": "root",
+                "ph": "X",
+                "ts": 1000.0,
+                "dur": 2.5,
+                "pid": pid,
+                "tid": 1,
+                "args": { "http.method": "GET", "http.status_code": 200, "cached": false },
+            }),
+            json!({
+                "name": "retry",
+                "ph": "i",
+                "ts": 1001.5,
+                "s": "t",
+                "pid": pid,
+                "tid": 1,
+                "args": { "attempt": 2 },
+            }),
+            json!({
+                "name": "late",
+                "ph": "X",
+                "ts": 3000.0,
+                "dur": 2.5,
+                "pid": pid,
+                "tid": 1,
+            }),
+        ]
+    );
+    assert_eq!(
+        read(&dir.path().join("00000000000000000000000000000002.json")).len(),
+        1
+    );
+}
+
+#[test]
+fn rolling_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = ChromeReporter::rolling(dir.path(), 2);
+
+    reporter.report(vec![span(1, 1, "a")]);
+    reporter.report(vec![span(2, 2, "b")]);
+    std::thread::sleep(std::time::Duration::from_millis(1));
+    reporter.report(vec![span(3, 3, "c"), span(3, 4, "d"), span(3, 5, "e")]);
+
+    let files = files(dir.path());
+    assert_eq!(files.len(), 2);
+    let names = |file: &String| -> Vec<Value> {
+        read(&dir.path().join(file))
+            .into_iter()
+            .map(|event| event["name"].clone())
+            .collect()
+    };
+    assert_eq!(names(&files[0]), vec!["a", "b"]);
+    assert_eq!(names(&files[1]), vec!["c", "d", "e"]);
+}
+
+#[test]
+fn thread_tracks() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = ChromeReporter::per_trace(dir.path());
+
+    reporter.report(vec![
+        SpanRecord {
+            thread_id: Some(7),
+            thread_name: Some("worker".into()),
+            ..span(1, 1, "a")
+        },
+        SpanRecord {
+            thread_id: Some(7),
+            thread_name: Some("worker".into()),
+            ..span(1, 2, "b")
+        },
+        SpanRecord {
+            thread_id: Some(8),
+            ..span(1, 3, "c")
+        },
+    ]);
+
+    let pid = std::process::id();
+    let events = read(&dir.path().join("00000000000000000000000000000001.json"));
+    assert_eq!(events.len(), 4);
+    assert_eq!(
+        events[0],
+        json!({
+            "name": "thread_name",
+            "ph": "M",
+            "ts": 0.0,
+            "pid": pid,
+            "tid": 7,
+            "args": { "name": "worker" },
+        })
+    );
+    let tids: Vec<_> = events[1..]
+        .iter()
+        .map(|event| event["tid"].clone())
+        .collect();
+    assert_eq!(tids, vec![7, 7, 8]);
+}
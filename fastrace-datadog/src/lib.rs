@@ -2,10 +2,14 @@
 
 #![doc = include_str!("../README.md")]
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 
 use fastrace::collector::Reporter;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
+use fastrace::collector::Value;
 use fastrace::prelude::*;
 use rmp_serde::Serializer;
 use serde::Serialize;
@@ -16,6 +20,7 @@ pub struct DatadogReporter {
     service_name: String,
     resource: String,
     trace_type: String,
+    type_from_kind: bool,
 }
 
 impl DatadogReporter {
@@ -30,33 +35,83 @@ impl DatadogReporter {
             service_name: service_name.into(),
             resource: resource.into(),
             trace_type: trace_type.into(),
+            type_from_kind: false,
         }
     }
 
+    /// Sets the `type` of server spans to `web`, and of producer and consumer spans to `queue`,
+    /// according to their [`SpanKind`]. Other spans keep the `trace_type` given to
+    /// [`DatadogReporter::new()`].
+    ///
+    /// Disabled by default.
+    pub fn with_type_from_kind(mut self, type_from_kind: bool) -> Self {
+        self.type_from_kind = type_from_kind;
+        self
+    }
+
     fn convert<'a>(&'a self, spans: &'a [SpanRecord]) -> Vec<DatadogSpan<'a>> {
         spans
             .iter()
-            .map(move |s| DatadogSpan {
-                name: &s.name,
-                service: &self.service_name,
-                trace_type: &self.trace_type,
-                resource: &self.resource,
-                start: s.begin_time_unix_ns as i64,
-                duration: s.duration_ns as i64,
-                meta: if s.properties.is_empty() {
-                    None
-                } else {
-                    Some(
-                        s.properties
-                            .iter()
-                            .map(|(k, v)| (k.as_ref(), v.as_ref()))
-                            .collect(),
-                    )
-                },
-                error_code: 0,
-                span_id: s.span_id.0,
-                trace_id: s.trace_id.0 as u64,
-                parent_id: s.parent_id.0,
+            .map(move |s| {
+                let mut meta = HashMap::new();
+                let mut metrics = HashMap::new();
+                for (k, v) in &s.properties {
+                    match v {
+                        Value::I64(v) => {
+                            metrics.insert(k.as_ref(), *v as f64);
+                        }
+                        Value::F64(v) => {
+                            metrics.insert(k.as_ref(), *v);
+                        }
+                        v => {
+                            meta.insert(k.as_ref(), meta_value(v));
+                        }
+                    }
+                }
+                if s.kind != SpanKind::Internal {
+                    meta.insert("span.kind", Cow::Borrowed(s.kind.as_str()));
+                }
+                if let Status::Error(message) = &s.status {
+                    if !message.is_empty() {
+                        meta.insert("error.message", Cow::Borrowed(message.as_ref()));
+                    }
+                }
+
+                DatadogSpan {
+                    name: &s.name,
+                    service: &self.service_name,
+                    trace_type: span_type(s.kind)
+                        .filter(|_| self.type_from_kind)
+                        .unwrap_or(&self.trace_type),
+                    resource: &self.resource,
+                    start: s.begin_time_unix_ns as i64,
+                    duration: s.duration_ns as i64,
+                    meta: (!meta.is_empty()).then_some(meta),
+                    metrics: (!metrics.is_empty()).then_some(metrics),
+                    error: matches!(s.status, Status::Error(_)) as i32,
+                    span_id: s.span_id.0,
+                    trace_id: s.trace_id.0 as u64,
+                    parent_id: s.parent_id.0,
+                    span_links: s
+                        .links
+                        .iter()
+                        .map(|link| DatadogSpanLink {
+                            trace_id: link.trace_id.0 as u64,
+                            trace_id_high: (link.trace_id.0 >> 64) as u64,
+                            span_id: link.span_id.0,
+                            attributes: if link.properties.is_empty() {
+                                None
+                            } else {
+                                Some(
+                                    link.properties
+                                        .iter()
+                                        .map(|(k, v)| (k.as_ref(), meta_value(v)))
+                                        .collect(),
+                                )
+                            },
+                        })
+                        .collect(),
+                }
             })
             .collect()
     }
@@ -81,6 +136,21 @@ impl DatadogReporter {
     }
 }
 
+fn span_type(kind: SpanKind) -> Option<&'static str> {
+    match kind {
+        SpanKind::Server => Some("web"),
+        SpanKind::Producer | SpanKind::Consumer => Some("queue"),
+        SpanKind::Client | SpanKind::Internal => None,
+    }
+}
+
+fn meta_value(value: &Value) -> Cow<'_, str> {
+    match value {
+        Value::Str(s) => Cow::Borrowed(s),
+        v => Cow::Owned(v.to_string()),
+    }
+}
+
 impl Reporter for DatadogReporter {
     fn report(&mut self, spans: Vec<SpanRecord>) {
         if spans.is_empty() {
@@ -103,9 +173,22 @@ struct DatadogSpan<'a> {
     start: i64,
     duration: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
-    meta: Option<HashMap<&'a str, &'a str>>,
-    error_code: i32,
+    meta: Option<HashMap<&'a str, Cow<'a, str>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metrics: Option<HashMap<&'a str, f64>>,
+    error: i32,
     span_id: u64,
     trace_id: u64,
     parent_id: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    span_links: Vec<DatadogSpanLink<'a>>,
+}
+
+#[derive(Serialize)]
+struct DatadogSpanLink<'a> {
+    trace_id: u64,
+    trace_id_high: u64,
+    span_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attributes: Option<HashMap<&'a str, Cow<'a, str>>>,
 }
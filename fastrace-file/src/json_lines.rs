@@ -0,0 +1,152 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The JSON lines encoding, one span record per line.
+
+use std::borrow::Cow;
+
+use fastrace::collector::EventRecord;
+use fastrace::collector::LinkRecord;
+use fastrace::collector::Status;
+use fastrace::collector::Value;
+use fastrace::prelude::*;
+use serde::Serialize;
+use serde::ser::SerializeMap;
+
+pub(crate) fn encode(spans: &[SpanRecord], buf: &mut Vec<u8>) -> serde_json::Result<()> {
+    for span in spans {
+        serde_json::to_writer(&mut *buf, &JsonSpan::new(span))?;
+        buf.push(b'\n');
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonSpan<'a> {
+    trace_id: TraceId,
+    span_id: SpanId,
+    parent_id: SpanId,
+    name: &'a str,
+    kind: &'static str,
+    begin_time_unix_ns: u64,
+    duration_ns: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<JsonStatus<'a>>,
+    #[serde(skip_serializing_if = "Properties::is_empty")]
+    properties: Properties<'a>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    events: Vec<JsonEvent<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    links: Vec<JsonLink<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_name: Option<&'a str>,
+}
+
+impl<'a> JsonSpan<'a> {
+    fn new(span: &'a SpanRecord) -> Self {
+        JsonSpan {
+            trace_id: span.trace_id,
+            span_id: span.span_id,
+            parent_id: span.parent_id,
+            name: &span.name,
+            kind: span.kind.as_str(),
+            begin_time_unix_ns: span.begin_time_unix_ns,
+            duration_ns: span.duration_ns,
+            status: match &span.status {
+                Status::Unset => None,
+                Status::Ok => Some(JsonStatus {
+                    code: "ok",
+                    message: None,
+                }),
+                Status::Error(message) => Some(JsonStatus {
+                    code: "error",
+                    message: Some(message),
+                }),
+            },
+            properties: Properties(&span.properties),
+            events: span.events.iter().map(JsonEvent::new).collect(),
+            links: span.links.iter().map(JsonLink::new).collect(),
+            trace_state: (!span.trace_state.is_empty())
+                .then(|| span.trace_state.encode_w3c_tracestate()),
+            thread_id: span.thread_id,
+            thread_name: span.thread_name.as_deref(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonStatus<'a> {
+    code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonEvent<'a> {
+    name: &'a str,
+    timestamp_unix_ns: u64,
+    #[serde(skip_serializing_if = "Properties::is_empty")]
+    properties: Properties<'a>,
+}
+
+impl<'a> JsonEvent<'a> {
+    fn new(event: &'a EventRecord) -> Self {
+        JsonEvent {
+            name: &event.name,
+            timestamp_unix_ns: event.timestamp_unix_ns,
+            properties: Properties(&event.properties),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLink<'a> {
+    trace_id: TraceId,
+    span_id: SpanId,
+    #[serde(skip_serializing_if = "Properties::is_empty")]
+    properties: Properties<'a>,
+}
+
+impl<'a> JsonLink<'a> {
+    fn new(link: &'a LinkRecord) -> Self {
+        JsonLink {
+            trace_id: link.trace_id,
+            span_id: link.span_id,
+            properties: Properties(&link.properties),
+        }
+    }
+}
+
+/// Serializes properties as a JSON object with native JSON values.
+struct Properties<'a>(&'a [(Cow<'static, str>, Value)]);
+
+impl Properties<'_> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Serialize for Properties<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (k, v) in self.0 {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
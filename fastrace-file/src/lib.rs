@@ -0,0 +1,258 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![doc = include_str!("../README.md")]
+
+mod json_lines;
+mod otlp;
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use fastrace::collector::Reporter;
+use fastrace::prelude::*;
+
+/// The encoding of the span files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// One JSON object per span record and per line.
+    #[default]
+    JsonLines,
+    /// One OTLP JSON `ExportTraceServiceRequest` per report and per line.
+    OtlpJson,
+}
+
+/// A reporter that writes spans into files in `dir`, named `spans-{timestamp}.jsonl`, or
+/// `spans-{timestamp}.jsonl.gz` when compressed.
+///
+/// A new file is started when the current one would exceed the
+/// [maximum size](FileReporter::with_max_file_size), or is older than the
+/// [maximum age](FileReporter::with_max_file_age). The files are flushed after each report.
+pub struct FileReporter {
+    dir: PathBuf,
+    format: Format,
+    service_name: String,
+    max_file_size: Option<u64>,
+    max_file_age: Option<Duration>,
+    #[cfg(feature = "gzip")]
+    gzip: bool,
+    current: Option<SpanFile>,
+}
+
+struct SpanFile {
+    output: Output,
+    size: u64,
+    opened_at: Instant,
+}
+
+enum Output {
+    Plain(BufWriter<File>),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<BufWriter<File>>),
+}
+
+impl FileReporter {
+    /// Creates a reporter that writes JSON lines into `dir`, without rotation.
+    pub fn new(dir: impl Into<PathBuf>) -> FileReporter {
+        FileReporter {
+            dir: dir.into(),
+            format: Format::default(),
+            service_name: "unknown_service".to_string(),
+            max_file_size: None,
+            max_file_age: None,
+            #[cfg(feature = "gzip")]
+            gzip: false,
+            current: None,
+        }
+    }
+
+    /// Sets the encoding of the files. Defaults to [`Format::JsonLines`].
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the `service.name` resource attribute of [`Format::OtlpJson`]. Defaults to
+    /// `unknown_service`.
+    pub fn with_service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.service_name = service_name.into();
+        self
+    }
+
+    /// Starts a new file when writing a report would make the current one exceed
+    /// `max_file_size` bytes, before compression. A single report larger than the limit is
+    /// written into its own file.
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// Starts a new file when the current one was opened more than `max_file_age` ago.
+    pub fn with_max_file_age(mut self, max_file_age: Duration) -> Self {
+        self.max_file_age = Some(max_file_age);
+        self
+    }
+
+    /// Compresses the files with gzip.
+    ///
+    /// A file is only a complete gzip stream once it is rotated, or the reporter is shut down
+    /// or dropped, though the data flushed after each report can already be decompressed.
+    #[cfg(feature = "gzip")]
+    pub fn with_gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+
+    fn try_report(&mut self, spans: Vec<SpanRecord>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        match self.format {
+            Format::JsonLines => json_lines::encode(&spans, &mut buf)?,
+            Format::OtlpJson => otlp::encode(&self.service_name, &spans, &mut buf)?,
+        }
+
+        if let Some(current) = &self.current {
+            if self.should_rotate(current, buf.len() as u64) {
+                self.close()?;
+            }
+        }
+        let current = match &mut self.current {
+            Some(current) => current,
+            None => self.current.insert(self.open()?),
+        };
+
+        current.output.write_all(&buf)?;
+        current.output.flush()?;
+        current.size += buf.len() as u64;
+        Ok(())
+    }
+
+    fn should_rotate(&self, current: &SpanFile, len: u64) -> bool {
+        let too_large = self
+            .max_file_size
+            .is_some_and(|max_file_size| current.size > 0 && current.size + len > max_file_size);
+        let too_old = self
+            .max_file_age
+            .is_some_and(|max_file_age| current.opened_at.elapsed() >= max_file_age);
+        too_large || too_old
+    }
+
+    fn open(&self) -> io::Result<SpanFile> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let extension = if self.is_gzip() { "jsonl.gz" } else { "jsonl" };
+        let mut timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros();
+        let file = loop {
+            let path = self.dir.join(format!("spans-{timestamp}.{extension}"));
+            match OpenOptions::new().write(true).create_new(true).open(path) {
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => timestamp += 1,
+                result => break result?,
+            }
+        };
+
+        let writer = BufWriter::new(file);
+        #[cfg(feature = "gzip")]
+        let output = if self.gzip {
+            Output::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            ))
+        } else {
+            Output::Plain(writer)
+        };
+        #[cfg(not(feature = "gzip"))]
+        let output = Output::Plain(writer);
+
+        Ok(SpanFile {
+            output,
+            size: 0,
+            opened_at: Instant::now(),
+        })
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        match self.current.take() {
+            Some(current) => current.output.finish(),
+            None => Ok(()),
+        }
+    }
+
+    fn is_gzip(&self) -> bool {
+        #[cfg(feature = "gzip")]
+        return self.gzip;
+        #[cfg(not(feature = "gzip"))]
+        return false;
+    }
+}
+
+impl Output {
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Output::Plain(mut writer) => writer.flush(),
+            #[cfg(feature = "gzip")]
+            Output::Gzip(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Plain(writer) => writer.write(buf),
+            #[cfg(feature = "gzip")]
+            Output::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Plain(writer) => writer.flush(),
+            #[cfg(feature = "gzip")]
+            Output::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+impl Reporter for FileReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        if let Err(err) = self.try_report(spans) {
+            log::error!("report to span file failed: {err}");
+        }
+    }
+
+    fn shutdown(&mut self) {
+        if let Err(err) = self.close() {
+            log::error!("failed to close span file: {err}");
+        }
+    }
+}
+
+impl Drop for FileReporter {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The [OTLP JSON](https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding) encoding,
+//! one `ExportTraceServiceRequest` per line.
+
+use std::borrow::Cow;
+
+use fastrace::collector::EventRecord;
+use fastrace::collector::LinkRecord;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
+use fastrace::collector::Value;
+use fastrace::prelude::*;
+use serde::Serialize;
+
+pub(crate) fn encode(
+    service_name: &str,
+    spans: &[SpanRecord],
+    buf: &mut Vec<u8>,
+) -> serde_json::Result<()> {
+    let request = ExportTraceServiceRequest {
+        resource_spans: [ResourceSpans {
+            resource: Resource {
+                attributes: vec![KeyValue {
+                    key: "service.name",
+                    value: AnyValue::String(Cow::Borrowed(service_name)),
+                }],
+            },
+            scope_spans: [ScopeSpans {
+                scope: InstrumentationScope {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                },
+                spans: spans.iter().map(Span::new).collect(),
+            }],
+        }],
+    };
+    serde_json::to_writer(&mut *buf, &request)?;
+    buf.push(b'\n');
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportTraceServiceRequest<'a> {
+    resource_spans: [ResourceSpans<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceSpans<'a> {
+    resource: Resource<'a>,
+    scope_spans: [ScopeSpans<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct Resource<'a> {
+    attributes: Vec<KeyValue<'a>>,
+}
+
+#[derive(Serialize)]
+struct ScopeSpans<'a> {
+    scope: InstrumentationScope,
+    spans: Vec<Span<'a>>,
+}
+
+#[derive(Serialize)]
+struct InstrumentationScope {
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Span<'a> {
+    trace_id: TraceId,
+    span_id: SpanId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_span_id: Option<SpanId>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    trace_state: String,
+    name: &'a str,
+    kind: u8,
+    start_time_unix_nano: String,
+    end_time_unix_nano: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<KeyValue<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    events: Vec<Event<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    links: Vec<Link<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<SpanStatus<'a>>,
+}
+
+impl<'a> Span<'a> {
+    fn new(span: &'a SpanRecord) -> Self {
+        let mut attributes = attributes(&span.properties);
+        if let Some(thread_id) = span.thread_id {
+            attributes.push(KeyValue {
+                key: "thread.id",
+                value: AnyValue::Int(thread_id.to_string()),
+            });
+        }
+        if let Some(thread_name) = &span.thread_name {
+            attributes.push(KeyValue {
+                key: "thread.name",
+                value: AnyValue::String(Cow::Borrowed(thread_name)),
+            });
+        }
+
+        Span {
+            trace_id: span.trace_id,
+            span_id: span.span_id,
+            parent_span_id: (span.parent_id.0 != 0).then_some(span.parent_id),
+            trace_state: span.trace_state.encode_w3c_tracestate(),
+            name: &span.name,
+            kind: span_kind(span.kind),
+            start_time_unix_nano: span.begin_time_unix_ns.to_string(),
+            end_time_unix_nano: (span.begin_time_unix_ns + span.duration_ns).to_string(),
+            attributes,
+            events: span.events.iter().map(Event::new).collect(),
+            links: span.links.iter().map(Link::new).collect(),
+            status: match &span.status {
+                Status::Unset => None,
+                Status::Ok => Some(SpanStatus {
+                    code: 1,
+                    message: None,
+                }),
+                Status::Error(message) => Some(SpanStatus {
+                    code: 2,
+                    message: (!message.is_empty()).then_some(message.as_ref()),
+                }),
+            },
+        }
+    }
+}
+
+/// Maps to the OTLP `SpanKind` enum values.
+fn span_kind(kind: SpanKind) -> u8 {
+    match kind {
+        SpanKind::Internal => 1,
+        SpanKind::Server => 2,
+        SpanKind::Client => 3,
+        SpanKind::Producer => 4,
+        SpanKind::Consumer => 5,
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Event<'a> {
+    time_unix_nano: String,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<KeyValue<'a>>,
+}
+
+impl<'a> Event<'a> {
+    fn new(event: &'a EventRecord) -> Self {
+        Event {
+            time_unix_nano: event.timestamp_unix_ns.to_string(),
+            name: &event.name,
+            attributes: attributes(&event.properties),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Link<'a> {
+    trace_id: TraceId,
+    span_id: SpanId,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<KeyValue<'a>>,
+}
+
+impl<'a> Link<'a> {
+    fn new(link: &'a LinkRecord) -> Self {
+        Link {
+            trace_id: link.trace_id,
+            span_id: link.span_id,
+            attributes: attributes(&link.properties),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SpanStatus<'a> {
+    code: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct KeyValue<'a> {
+    key: &'a str,
+    value: AnyValue<'a>,
+}
+
+/// 64-bit integers are encoded as strings, following the protobuf JSON mapping.
+#[derive(Serialize)]
+enum AnyValue<'a> {
+    #[serde(rename = "stringValue")]
+    String(Cow<'a, str>),
+    #[serde(rename = "intValue")]
+    Int(String),
+    #[serde(rename = "doubleValue")]
+    Double(f64),
+    #[serde(rename = "boolValue")]
+    Bool(bool),
+    #[serde(rename = "bytesValue")]
+    Bytes(String),
+    #[serde(rename = "arrayValue")]
+    Array(ArrayValue<'a>),
+}
+
+#[derive(Serialize)]
+struct ArrayValue<'a> {
+    values: Vec<AnyValue<'a>>,
+}
+
+fn attributes<'a>(properties: &'a [(Cow<'static, str>, Value)]) -> Vec<KeyValue<'a>> {
+    properties
+        .iter()
+        .map(|(k, v)| KeyValue {
+            key: k,
+            value: any_value(v),
+        })
+        .collect()
+}
+
+fn any_value(value: &Value) -> AnyValue<'_> {
+    match value {
+        Value::Str(s) => AnyValue::String(Cow::Borrowed(s)),
+        Value::I64(v) => AnyValue::Int(v.to_string()),
+        Value::F64(v) => AnyValue::Double(*v),
+        Value::Bool(v) => AnyValue::Bool(*v),
+        Value::Bytes(bytes) => AnyValue::Bytes(base64(bytes)),
+        Value::Array(values) => AnyValue::Array(ArrayValue {
+            values: values.iter().map(any_value).collect(),
+        }),
+        value => AnyValue::String(Cow::Owned(value.to_string())),
+    }
+}
+
+/// Encodes `bytes` in standard base64 with padding, as protobuf JSON encodes `bytes` fields.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_encoding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(&[0, 255, 254]), "AP/+");
+    }
+}
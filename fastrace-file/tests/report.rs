@@ -0,0 +1,224 @@
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+use fastrace::collector::EventRecord;
+use fastrace::collector::LinkRecord;
+use fastrace::collector::Reporter;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
+use fastrace::collector::TraceState;
+use fastrace::collector::Value;
+use fastrace::prelude::*;
+use fastrace_file::FileReporter;
+use fastrace_file::Format;
+use serde_json::json;
+
+fn span_record() -> SpanRecord {
+    SpanRecord {
+        trace_id: TraceId(0x0af7651916cd43dd8448eb211c80319c),
+        span_id: SpanId(2),
+        parent_id: SpanId(1),
+        begin_time_unix_ns: 1_000,
+        duration_ns: 500,
+        name: "query".into(),
+        properties: vec![
+            ("db.system".into(), "postgresql".into()),
+            ("db.rows".into(), 3i64.into()),
+            ("bytes".into(), Value::Bytes(vec![0, 255, 254].into())),
+        ],
+        events: vec![EventRecord {
+            name: "retry".into(),
+            timestamp_unix_ns: 1_200,
+            properties: vec![("attempt".into(), 2i64.into())],
+        }],
+        links: vec![LinkRecord {
+            trace_id: TraceId(3),
+            span_id: SpanId(4),
+            sampled: true,
+            properties: vec![],
+        }],
+        status: Status::Error("timeout".into()),
+        kind: SpanKind::Client,
+        trace_state: TraceState::decode_w3c_tracestate("congo=t61rcWkgMzE"),
+        thread_id: Some(7),
+        thread_name: Some("worker".into()),
+    }
+}
+
+fn files(dir: &Path) -> Vec<String> {
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    files
+}
+
+fn lines(path: &Path) -> Vec<serde_json::Value> {
+    let mut content = String::new();
+    if path.extension().unwrap() == "gz" {
+        flate2::read::GzDecoder::new(std::fs::File::open(path).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+    } else {
+        content = std::fs::read_to_string(path).unwrap();
+    }
+    content
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn json_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = FileReporter::new(dir.path());
+    reporter.report(vec![span_record(), SpanRecord {
+        name: "root".into(),
+        ..SpanRecord::default()
+    }]);
+
+    let files = files(dir.path());
+    assert_eq!(files.len(), 1);
+    assert!(files[0].starts_with("spans-") && files[0].ends_with(".jsonl"));
+    assert_eq!(lines(&dir.path().join(&files[0])), vec![
+        json!({
+            "trace_id": "0af7651916cd43dd8448eb211c80319c",
+            "span_id": "0000000000000002",
+            "parent_id": "0000000000000001",
+            "name": "query",
+            "kind": "client",
+            "begin_time_unix_ns": 1000,
+            "duration_ns": 500,
+            "status": { "code": "error", "message": "timeout" },
+            "properties": { "db.system": "postgresql", "db.rows": 3, "bytes": "00fffe" },
+            "events": [
+                { "name": "retry", "timestamp_unix_ns": 1200, "properties": { "attempt": 2 } },
+            ],
+            "links": [
+                { "trace_id": "00000000000000000000000000000003", "span_id": "0000000000000004" },
+            ],
+            "trace_state": "congo=t61rcWkgMzE",
+            "thread_id": 7,
+            "thread_name": "worker",
+        }),
+        json!({
+            "trace_id": "00000000000000000000000000000000",
+            "span_id": "0000000000000000",
+            "parent_id": "0000000000000000",
+            "name": "root",
+            "kind": "internal",
+            "begin_time_unix_ns": 0,
+            "duration_ns": 0,
+        }),
+    ]);
+}
+
+#[test]
+fn otlp_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = FileReporter::new(dir.path())
+        .with_format(Format::OtlpJson)
+        .with_service_name("test-service");
+    reporter.report(vec![span_record()]);
+    reporter.report(vec![SpanRecord::default()]);
+
+    let files = files(dir.path());
+    assert_eq!(files.len(), 1);
+    let lines = lines(&dir.path().join(&files[0]));
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [
+                        { "key": "service.name", "value": { "stringValue": "test-service" } },
+                    ],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": "fastrace-file", "version": env!("CARGO_PKG_VERSION") },
+                    "spans": [{
+                        "traceId": "0af7651916cd43dd8448eb211c80319c",
+                        "spanId": "0000000000000002",
+                        "parentSpanId": "0000000000000001",
+                        "traceState": "congo=t61rcWkgMzE",
+                        "name": "query",
+                        "kind": 3,
+                        "startTimeUnixNano": "1000",
+                        "endTimeUnixNano": "1500",
+                        "attributes": [
+                            { "key": "db.system", "value": { "stringValue": "postgresql" } },
+                            { "key": "db.rows", "value": { "intValue": "3" } },
+                            { "key": "bytes", "value": { "bytesValue": "AP/+" } },
+                            { "key": "thread.id", "value": { "intValue": "7" } },
+                            { "key": "thread.name", "value": { "stringValue": "worker" } },
+                        ],
+                        "events": [{
+                            "timeUnixNano": "1200",
+                            "name": "retry",
+                            "attributes": [{ "key": "attempt", "value": { "intValue": "2" } }],
+                        }],
+                        "links": [{
+                            "traceId": "00000000000000000000000000000003",
+                            "spanId": "0000000000000004",
+                        }],
+                        "status": { "code": 2, "message": "timeout" },
+                    }],
+                }],
+            }],
+        })
+    );
+    assert_eq!(
+        lines[1]["resourceSpans"][0]["scopeSpans"][0]["spans"][0]["kind"],
+        1
+    );
+}
+
+#[test]
+fn rotation_by_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = FileReporter::new(dir.path()).with_max_file_size(1);
+    reporter.report(vec![span_record(), span_record()]);
+    reporter.report(vec![span_record()]);
+    reporter.report(vec![span_record()]);
+
+    let files = files(dir.path());
+    let counts: Vec<_> = files
+        .iter()
+        .map(|file| lines(&dir.path().join(file)).len())
+        .collect();
+    assert_eq!(counts, vec![2, 1, 1]);
+}
+
+#[test]
+fn rotation_by_age() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = FileReporter::new(dir.path()).with_max_file_age(Duration::from_millis(50));
+    reporter.report(vec![span_record()]);
+    reporter.report(vec![span_record()]);
+    std::thread::sleep(Duration::from_millis(60));
+    reporter.report(vec![span_record()]);
+
+    let files = files(dir.path());
+    let counts: Vec<_> = files
+        .iter()
+        .map(|file| lines(&dir.path().join(file)).len())
+        .collect();
+    assert_eq!(counts, vec![2, 1]);
+}
+
+#[test]
+fn gzip() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reporter = FileReporter::new(dir.path()).with_gzip(true);
+    reporter.report(vec![span_record()]);
+    reporter.report(vec![span_record()]);
+    reporter.shutdown();
+
+    let files = files(dir.path());
+    assert_eq!(files.len(), 1);
+    assert!(files[0].ends_with(".jsonl.gz"));
+    assert_eq!(lines(&dir.path().join(&files[0])).len(), 2);
+}
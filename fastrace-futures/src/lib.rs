@@ -7,6 +7,7 @@ use std::task::Context;
 use std::task::Poll;
 
 use fastrace::Span;
+use fastrace::future::PollStats;
 use futures_core::Stream;
 use futures_sink::Sink;
 
@@ -49,6 +50,7 @@ pub trait StreamExt: Stream + Sized {
         InSpan {
             inner: self,
             span: Some(span),
+            poll_stats: None,
         }
     }
 }
@@ -87,6 +89,7 @@ pub trait SinkExt<Item>: Sink<Item> + Sized {
         InSpan {
             inner: self,
             span: Some(span),
+            poll_stats: None,
         }
     }
 }
@@ -95,11 +98,61 @@ impl<T, Item> SinkExt<Item> for T where T: Sink<Item> {}
 
 /// Adapter for [`StreamExt::in_span()`](StreamExt::in_span) and
 /// [`SinkExt::in_span()`](SinkExt::in_span).
-#[pin_project::pin_project]
+#[pin_project::pin_project(PinnedDrop)]
 pub struct InSpan<T> {
     #[pin]
     inner: T,
     span: Option<Span>,
+    poll_stats: Option<PollStats>,
+}
+
+impl<T> InSpan<T> {
+    /// Accumulates the time spent in polls, the time spent waiting between polls and the
+    /// number of polls, and records them on the span when the stream is finished, the sink is
+    /// closed, or the adapter is dropped, as the [`POLL_BUSY_NS_PROPERTY`],
+    /// [`POLL_IDLE_NS_PROPERTY`] and [`POLL_COUNT_PROPERTY`] properties.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use fastrace::prelude::*;
+    /// use fastrace_futures::StreamExt as _;
+    /// use futures::StreamExt;
+    ///
+    /// let root = Span::root("root", SpanContext::random());
+    /// let s = futures::stream::iter([1, 2])
+    ///     .in_span(Span::enter_with_parent("task", &root))
+    ///     .with_poll_stats();
+    ///
+    /// assert_eq!(s.collect::<Vec<_>>().await, vec![1, 2]);
+    /// # }
+    /// ```
+    ///
+    /// [`POLL_BUSY_NS_PROPERTY`]: fastrace::future::POLL_BUSY_NS_PROPERTY
+    /// [`POLL_IDLE_NS_PROPERTY`]: fastrace::future::POLL_IDLE_NS_PROPERTY
+    /// [`POLL_COUNT_PROPERTY`]: fastrace::future::POLL_COUNT_PROPERTY
+    pub fn with_poll_stats(mut self) -> Self {
+        self.poll_stats = Some(PollStats::default());
+        self
+    }
+}
+
+/// Runs `poll`, measuring it if poll stats are enabled.
+#[inline]
+fn measure<R>(poll_stats: &mut Option<PollStats>, poll: impl FnOnce() -> R) -> R {
+    match poll_stats {
+        Some(poll_stats) => poll_stats.measure(poll),
+        None => poll(),
+    }
+}
+
+/// Ends the span, recording the poll stats on it if enabled.
+fn finish(span: &mut Option<Span>, poll_stats: &Option<PollStats>) {
+    if let (Some(span), Some(poll_stats)) = (span.take(), poll_stats) {
+        poll_stats.record(&span);
+    }
 }
 
 impl<T> Stream for InSpan<T>
@@ -111,13 +164,13 @@ where T: Stream
         let this = self.project();
 
         let _guard = this.span.as_ref().map(|s| s.set_local_parent());
-        let res = this.inner.poll_next(cx);
+        let res = measure(this.poll_stats, || this.inner.poll_next(cx));
 
         match res {
             Poll::Pending => Poll::Pending,
             Poll::Ready(None) => {
                 // finished
-                this.span.take();
+                finish(this.span, this.poll_stats);
                 Poll::Ready(None)
             }
             Poll::Ready(Some(item)) => Poll::Ready(Some(item)),
@@ -133,7 +186,7 @@ where T: Sink<I>
     fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         let this = self.project();
         let _guard = this.span.as_ref().map(|s| s.set_local_parent());
-        this.inner.poll_ready(cx)
+        measure(this.poll_stats, || this.inner.poll_ready(cx))
     }
 
     fn start_send(self: Pin<&mut Self>, item: I) -> Result<(), Self::Error> {
@@ -145,22 +198,32 @@ where T: Sink<I>
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         let this = self.project();
         let _guard = this.span.as_ref().map(|s| s.set_local_parent());
-        this.inner.poll_flush(cx)
+        measure(this.poll_stats, || this.inner.poll_flush(cx))
     }
 
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         let this = self.project();
 
         let _guard = this.span.as_ref().map(|s| s.set_local_parent());
-        let res = this.inner.poll_close(cx);
+        let res = measure(this.poll_stats, || this.inner.poll_close(cx));
 
         match res {
             r @ Poll::Pending => r,
             other => {
                 // closed
-                this.span.take();
+                finish(this.span, this.poll_stats);
                 other
             }
         }
     }
 }
+
+#[pin_project::pinned_drop]
+impl<T> PinnedDrop for InSpan<T> {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        if let (Some(span), Some(poll_stats)) = (this.span.as_ref(), this.poll_stats.as_ref()) {
+            poll_stats.record(span);
+        }
+    }
+}
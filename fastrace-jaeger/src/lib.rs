@@ -9,6 +9,9 @@ use std::net::SocketAddr;
 use std::net::UdpSocket;
 
 use fastrace::collector::Reporter;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
+use fastrace::collector::Value;
 use fastrace::prelude::*;
 use thrift::Log;
 use thrift_codec::CompactEncode;
@@ -18,6 +21,8 @@ use crate::thrift::Batch;
 use crate::thrift::EmitBatchNotification;
 use crate::thrift::JaegerSpan;
 use crate::thrift::Process;
+use crate::thrift::SpanRef;
+use crate::thrift::SpanRefKind;
 use crate::thrift::Tag;
 
 /// [Jaeger](https://www.jaegertracing.io/) reporter for `fastrace` via UDP endpoint.
@@ -57,30 +62,35 @@ impl JaegerReporter {
                 span_id: s.span_id.0 as i64,
                 parent_span_id: s.parent_id.0 as i64,
                 operation_name: s.name.to_string(),
-                references: vec![],
+                references: s
+                    .links
+                    .iter()
+                    .map(|link| SpanRef {
+                        kind: SpanRefKind::FollowsFrom,
+                        trace_id_low: link.trace_id.0 as i64,
+                        trace_id_high: (link.trace_id.0 >> 64) as i64,
+                        span_id: link.span_id.0 as i64,
+                    })
+                    .collect(),
                 flags: 1,
                 start_time: (s.begin_time_unix_ns / 1_000) as i64,
                 duration: (s.duration_ns / 1_000) as i64,
                 tags: s
                     .properties
                     .iter()
-                    .map(|(k, v)| Tag::String {
-                        key: k.to_string(),
-                        value: v.to_string(),
-                    })
+                    .map(|(k, v)| tag(k, v))
+                    .chain(kind_tag(s.kind))
+                    .chain(status_tags(&s.status))
                     .collect(),
                 logs: s
                     .events
                     .iter()
                     .map(|event| Log {
                         timestamp: (event.timestamp_unix_ns / 1_000) as i64,
-                        fields: [("name".into(), event.name.clone())]
+                        fields: [("name".into(), Value::Str(event.name.clone()))]
                             .iter()
                             .chain(&event.properties)
-                            .map(|(k, v)| Tag::String {
-                                key: k.to_string(),
-                                value: v.to_string(),
-                            })
+                            .map(|(k, v)| tag(k, v))
                             .collect(),
                     })
                     .collect(),
@@ -132,6 +142,54 @@ impl JaegerReporter {
     }
 }
 
+fn tag(key: &str, value: &Value) -> Tag {
+    let key = key.to_string();
+    match value {
+        Value::Str(value) => Tag::String {
+            key,
+            value: value.to_string(),
+        },
+        Value::I64(value) => Tag::Long { key, value: *value },
+        Value::F64(value) => Tag::Double { key, value: *value },
+        Value::Bool(value) => Tag::Bool { key, value: *value },
+        Value::Bytes(value) => Tag::Binary {
+            key,
+            value: value.to_vec(),
+        },
+        value => Tag::String {
+            key,
+            value: value.to_string(),
+        },
+    }
+}
+
+fn kind_tag(kind: SpanKind) -> Option<Tag> {
+    (kind != SpanKind::Internal).then(|| Tag::String {
+        key: "span.kind".to_string(),
+        value: kind.as_str().to_string(),
+    })
+}
+
+fn status_tags(status: &Status) -> Vec<Tag> {
+    match status {
+        Status::Unset | Status::Ok => vec![],
+        Status::Error(message) if message.is_empty() => vec![Tag::Bool {
+            key: "error".to_string(),
+            value: true,
+        }],
+        Status::Error(message) => vec![
+            Tag::Bool {
+                key: "error".to_string(),
+                value: true,
+            },
+            Tag::String {
+                key: "error.message".to_string(),
+                value: message.to_string(),
+            },
+        ],
+    }
+}
+
 impl Reporter for JaegerReporter {
     fn report(&mut self, spans: Vec<SpanRecord>) {
         if spans.is_empty() {
@@ -41,8 +41,34 @@ use crate::visit_mut::VisitMut;
 ///   used. Only available for `async fn`. Defaults to `false`.
 /// * `properties` - A list of key-value pairs to be added as properties to the span. The value can
 ///   be a format string, where the function arguments are accessible. Defaults to `{}`.
+/// * `fields` - A list of properties whose values are expressions, written as `key = expr` to
+///   convert the value into a property value, `key = %expr` to record its `Display`, or `key =
+///   ?expr` to record its `Debug`. A bare argument name records the `Debug` of that argument. Keys
+///   can be identifiers separated by dots, or string literals.
+/// * `skip` - A list of arguments that are not recorded.
+/// * `skip_all` - Whether no argument is recorded, other than those listed in `fields`.
+/// * `ret` - Whether to record the return value as the `return` property, using its `Debug`, or its
+///   `Display` with `ret(Display)`.
+/// * `err` - Whether to set the status of the span to error with the message of the error, using
+///   its `Display`, or its `Debug` with `err(Debug)`, when the function returns `Err`. Only
+///   available for functions returning a `Result`. With `ret`, only the `Ok` value is recorded.
+/// * `level` - The verbosity level of the span, one of `"error"`, `"warn"`, `"info"`, `"debug"` or
+///   `"trace"`. The span is only created if the level is enabled by `fastrace::set_max_level()`, or
+///   by the `fastrace::Filter` directive for the module of the function. Without a level, the span
+///   is created unless a filter directive disables it.
+/// * `if` - A boolean expression, where the function arguments are accessible. The span is only
+///   created if it evaluates to `true`.
 /// * `crate` - The path to the fastrace crate. Defaults to `::fastrace`.
 ///
+/// The decision of the filter and the level is cached for each annotated function, so a disabled
+/// span costs a single relaxed atomic load. When the span is not created, the function runs as if
+/// it were not annotated. Spans created by `enter_on_poll` are not filtered, and `level` and `if`
+/// can not be used with `enter_on_poll`.
+///
+/// Once any of `fields`, `skip` or `skip_all` is given, the `Debug` of every argument, except
+/// `self` and the skipped ones, is recorded as a property named after the argument. Naming an
+/// argument that does not exist in `fields` or `skip` is a compile error.
+///
 /// # Examples
 ///
 /// ```
@@ -67,9 +93,27 @@ use crate::visit_mut::VisitMut;
 /// async fn properties(a: u64) {
 ///     // ...
 /// }
+///
+/// #[trace(skip(password), fields(user.len = user.len(), host = %host))]
+/// fn fields(user: &str, password: &str, host: std::net::Ipv4Addr) {
+///     // ...
+/// }
+///
+/// #[trace(level = "debug", if = n > 0)]
+/// fn step(n: u64) {
+///     // ...
+/// }
+///
+/// #[trace(ret, err)]
+/// fn parse(s: &str) -> Result<u64, std::num::ParseIntError> {
+///     let n = s.parse::<u64>()?;
+///     Ok(n * 2)
+/// }
 /// ```
 ///
-/// The code snippets above will be expanded to:
+/// The code snippets above will be expanded to the following, where the check of the filter
+/// decision cached for the function, shown in `step()`, is omitted from the other functions for
+/// brevity:
 ///
 /// ```
 /// # use fastrace::prelude::*;
@@ -112,6 +156,58 @@ use crate::visit_mut::VisitMut;
 ///     .in_span(__span__)
 ///     .await
 /// }
+///
+/// fn fields(user: &str, password: &str, host: std::net::Ipv4Addr) {
+///     let __guard__ =
+///         LocalSpan::enter_with_local_parent("example::fields").with_properties(|| {
+///             [
+///                 (
+///                     std::borrow::Cow::from("user"),
+///                     fastrace::collector::Value::from(format!("{:?}", user)),
+///                 ),
+///                 (
+///                     std::borrow::Cow::from("user.len"),
+///                     fastrace::collector::Value::from(user.len()),
+///                 ),
+///                 (
+///                     std::borrow::Cow::from("host"),
+///                     fastrace::collector::Value::from(format!("{}", host)),
+///                 ),
+///             ]
+///         });
+///     // ...
+/// }
+///
+/// fn step(n: u64) {
+///     let __enabled__ = {
+///         static __CALLSITE__: fastrace::Callsite =
+///             fastrace::Callsite::new(module_path!(), Some(fastrace::Level::Debug));
+///         __CALLSITE__.enabled("example::step")
+///     } && (n > 0);
+///     let __guard__ = if __enabled__ {
+///         LocalSpan::enter_with_local_parent("example::step")
+///     } else {
+///         LocalSpan::default()
+///     };
+///     // ...
+/// }
+///
+/// fn parse(s: &str) -> Result<u64, std::num::ParseIntError> {
+///     let __guard__ = LocalSpan::enter_with_local_parent("example::parse");
+///     // A `return` or `?` returns from the closure, so that the return value is always recorded.
+///     let __ret__: Result<u64, std::num::ParseIntError> =
+///         fastrace::util::call_once(move || -> Result<u64, std::num::ParseIntError> {
+///             let n = s.parse::<u64>()?;
+///             Ok(n * 2)
+///         });
+///     match &__ret__ {
+///         Ok(__ok__) => LocalSpan::add_property(|| ("return", format!("{:?}", __ok__))),
+///         Err(__err__) => LocalSpan::set_status(fastrace::collector::Status::Error(
+///             std::borrow::Cow::from(format!("{}", __err__)),
+///         )),
+///     }
+///     __ret__
+/// }
 /// ```
 #[proc_macro_attribute]
 #[proc_macro_error]
@@ -128,6 +224,7 @@ pub fn trace(
     let args = parse_macro_input!(args as Args);
     let input = parse_macro_input!(item as ItemFn);
     let func_name = &input.sig.ident;
+    let arg_idents = arg_idents(&input.sig);
 
     // Check for async_trait-like patterns in the block, and instrument
     // the future instead of the wrapper.
@@ -143,8 +240,15 @@ pub fn trace(
             }
             // async-trait >= 0.1.44
             AsyncTraitKind::Async(async_expr) => {
-                let instrumented_block =
-                    gen_block(func_name, &async_expr.block, true, false, &args, None);
+                let instrumented_block = gen_block(
+                    func_name,
+                    &async_expr.block,
+                    true,
+                    false,
+                    &args,
+                    &arg_idents,
+                    get_async_trait_output(&input.sig.output),
+                );
                 let async_attrs = &async_expr.attrs;
                 quote::quote! {
                     Box::pin(#(#async_attrs) * #instrumented_block)
@@ -162,6 +266,7 @@ pub fn trace(
             input.sig.asyncness.is_some(),
             input.sig.asyncness.is_some(),
             &args,
+            &arg_idents,
             Some(output_ty),
         )
     };
@@ -203,9 +308,76 @@ struct Args {
     short_name: bool,
     enter_on_poll: bool,
     properties: Vec<(LitStr, LitStr)>,
+    fields: Vec<Field>,
+    skip: Vec<Ident>,
+    skip_all: bool,
+    ret: Option<Format>,
+    err: Option<Format>,
+    level: Option<Level>,
+    cond: Option<Expr>,
     crate_path: Path,
 }
 
+struct Level {
+    lit: LitStr,
+    /// The name of the `fastrace::Level` variant.
+    variant: String,
+}
+
+impl Parse for Level {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lit: LitStr = input.parse()?;
+        let variant = match lit.value().to_ascii_lowercase().as_str() {
+            "error" => "Error",
+            "warn" => "Warn",
+            "info" => "Info",
+            "debug" => "Debug",
+            "trace" => "Trace",
+            _ => {
+                return Err(Error::new(
+                    lit.span(),
+                    "expected one of `error`, `warn`, `info`, `debug` or `trace`",
+                ));
+            }
+        };
+        Ok(Level {
+            lit,
+            variant: variant.to_string(),
+        })
+    }
+}
+
+/// The trait used to format a value into a property.
+#[derive(Clone, Copy)]
+enum Format {
+    Debug,
+    Display,
+}
+
+impl Format {
+    fn parse_optional(input: ParseStream, default: Format) -> Result<Self> {
+        if !input.peek(token::Paren) {
+            return Ok(default);
+        }
+
+        let content;
+        let _paren_token = parenthesized!(content in input);
+        let format: Ident = content.parse()?;
+        match format.to_string().as_str() {
+            "Debug" => Ok(Format::Debug),
+            "Display" => Ok(Format::Display),
+            _ => Err(Error::new(format.span(), "expected `Debug` or `Display`")),
+        }
+    }
+
+    fn format(self, value: &Ident) -> proc_macro2::TokenStream {
+        match self {
+            Format::Debug => quote!(std::format!("{:?}", #value)),
+            Format::Display => quote!(std::format!("{}", #value)),
+        }
+    }
+}
+
 impl Default for Args {
     fn default() -> Self {
         Self {
@@ -213,11 +385,25 @@ impl Default for Args {
             short_name: false,
             enter_on_poll: false,
             properties: Vec::new(),
+            fields: Vec::new(),
+            skip: Vec::new(),
+            skip_all: false,
+            ret: None,
+            err: None,
+            level: None,
+            cond: None,
             crate_path: parse_quote!(::fastrace),
         }
     }
 }
 
+impl Args {
+    /// Whether the arguments of the function are recorded as properties.
+    fn record_args(&self) -> bool {
+        !self.skip_all && (!self.fields.is_empty() || !self.skip.is_empty())
+    }
+}
+
 struct Property {
     key: LitStr,
     value: LitStr,
@@ -232,16 +418,94 @@ impl Parse for Property {
     }
 }
 
+struct Field {
+    key: LitStr,
+    value: FieldValue,
+}
+
+enum FieldValue {
+    /// A bare argument name, recorded with `Debug`.
+    Arg(Ident),
+    /// `key = expr`, converted into a property value.
+    Value(Expr),
+    /// `key = %expr`, recorded with `Display`.
+    Display(Expr),
+    /// `key = ?expr`, recorded with `Debug`.
+    Debug(Expr),
+}
+
+impl Parse for Field {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let (key, ident) = if input.peek(LitStr) {
+            (input.parse::<LitStr>()?, None)
+        } else {
+            let first: Ident = input.parse()?;
+            let mut key = first.to_string();
+            let mut dotted = false;
+            while input.peek(Token![.]) {
+                input.parse::<Token![.]>()?;
+                let part: Ident = input.parse()?;
+                key.push('.');
+                key.push_str(&part.to_string());
+                dotted = true;
+            }
+            (LitStr::new(&key, first.span()), (!dotted).then_some(first))
+        };
+
+        if let Some(ident) = ident {
+            if input.is_empty() || input.peek(Token![,]) {
+                return Ok(Field {
+                    key,
+                    value: FieldValue::Arg(ident),
+                });
+            }
+        }
+
+        input.parse::<Token![=]>()?;
+        let value = if input.peek(Token![%]) {
+            input.parse::<Token![%]>()?;
+            FieldValue::Display(input.parse()?)
+        } else if input.peek(Token![?]) {
+            input.parse::<Token![?]>()?;
+            FieldValue::Debug(input.parse()?)
+        } else {
+            FieldValue::Value(input.parse()?)
+        };
+        Ok(Field { key, value })
+    }
+}
+
 impl Parse for Args {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut name = None;
         let mut short_name = false;
         let mut enter_on_poll = false;
         let mut properties = Vec::new();
+        let mut fields = Vec::new();
+        let mut skip = Vec::new();
+        let mut skip_all = false;
+        let mut ret = None;
+        let mut err = None;
+        let mut level = None;
+        let mut cond = None;
         let mut crate_path = parse_quote!(::fastrace);
         let mut seen = HashSet::new();
 
         while !input.is_empty() {
+            // `if` is a keyword, so it can not be parsed as the other keys.
+            if input.peek(Token![if]) {
+                let if_token = input.parse::<Token![if]>()?;
+                if cond.is_some() {
+                    return Err(Error::new(if_token.span, "duplicate argument"));
+                }
+                input.parse::<Token![=]>()?;
+                cond = Some(input.parse::<Expr>()?);
+                if !input.is_empty() {
+                    let _ = input.parse::<Token![,]>();
+                }
+                continue;
+            }
+
             let key: Path = input.parse()?;
             let key = key
                 .get_ident()
@@ -250,12 +514,41 @@ impl Parse for Args {
                 return Err(Error::new(key.span(), "duplicate argument"));
             }
             seen.insert(key.clone());
-            input.parse::<Token![=]>()?;
             match key.to_string().as_str() {
+                "skip_all" => {
+                    skip_all = true;
+                }
+                "skip" => {
+                    let content;
+                    let _paren_token = parenthesized!(content in input);
+                    let skip_list = content.parse_terminated(Ident::parse, Token![,])?;
+                    skip.extend(skip_list);
+                }
+                "fields" => {
+                    let content;
+                    let _paren_token = parenthesized!(content in input);
+                    let field_list = content.parse_terminated(Field::parse, Token![,])?;
+                    fields.extend(field_list);
+                }
+                "ret" => {
+                    ret = Some(Format::parse_optional(input, Format::Debug)?);
+                }
+                "err" => {
+                    err = Some(Format::parse_optional(input, Format::Display)?);
+                }
+                _ => {
+                    input.parse::<Token![=]>()?;
+                }
+            }
+            match key.to_string().as_str() {
+                "skip_all" | "skip" | "fields" | "ret" | "err" => {}
                 "name" => {
                     let parsed_name: LitStr = input.parse()?;
                     name = Some(parsed_name);
                 }
+                "level" => {
+                    level = Some(input.parse::<Level>()?);
+                }
                 "short_name" => {
                     let parsed_short_name: LitBool = input.parse()?;
                     short_name = parsed_short_name.value;
@@ -291,6 +584,13 @@ impl Parse for Args {
             short_name,
             enter_on_poll,
             properties,
+            fields,
+            skip,
+            skip_all,
+            ret,
+            err,
+            level,
+            cond,
             crate_path,
         })
     }
@@ -315,9 +615,10 @@ fn gen_name(func_name: &Ident, args: &Args) -> proc_macro2::TokenStream {
     }
 }
 
-fn gen_properties(args: &Args) -> proc_macro2::TokenStream {
+fn gen_properties(args: &Args, arg_idents: &[Ident]) -> proc_macro2::TokenStream {
+    let fields = gen_fields(args, arg_idents);
     if args.properties.is_empty() {
-        return quote::quote!();
+        return fields;
     }
 
     if args.enter_on_poll {
@@ -336,11 +637,166 @@ fn gen_properties(args: &Args) -> proc_macro2::TokenStream {
         )
     });
     let properties = Punctuated::<_, Token![,]>::from_iter(properties);
+    quote!(
+        .with_properties(|| [ #properties ]) #fields
+    )
+}
+
+fn gen_fields(args: &Args, arg_idents: &[Ident]) -> proc_macro2::TokenStream {
+    if args.skip_all && !args.skip.is_empty() {
+        abort_call_site!("`skip` and `skip_all` can not be used together")
+    }
+    for ident in &args.skip {
+        if !arg_idents.contains(ident) {
+            abort!(ident.span(), "unknown argument `{}`", ident)
+        }
+    }
+    for (i, field) in args.fields.iter().enumerate() {
+        if let FieldValue::Arg(ident) = &field.value {
+            if !arg_idents.contains(ident) {
+                abort!(ident.span(), "unknown argument `{}`", ident)
+            }
+        }
+        let is_duplicate = args
+            .properties
+            .iter()
+            .any(|(k, _)| k.value() == field.key.value())
+            || args.fields[..i]
+                .iter()
+                .any(|other| other.key.value() == field.key.value());
+        if is_duplicate {
+            abort!(field.key.span(), "duplicate property key")
+        }
+    }
+
+    let recorded_args: Vec<_> = if args.record_args() {
+        arg_idents
+            .iter()
+            .filter(|ident| !args.skip.contains(ident))
+            .filter(|ident| {
+                !args
+                    .fields
+                    .iter()
+                    .any(|field| field.key.value() == ident.to_string())
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    if recorded_args.is_empty() && args.fields.is_empty() {
+        return quote!();
+    }
+
+    if args.enter_on_poll {
+        abort_call_site!("`enter_on_poll` can not be used with `fields`")
+    }
+
+    let crate_path = &args.crate_path;
+    let recorded_args = recorded_args.into_iter().map(|ident| {
+        let key = LitStr::new(&ident.to_string(), ident.span());
+        quote!(
+            (std::borrow::Cow::from(#key), #crate_path::collector::Value::from(std::format!("{:?}", #ident)))
+        )
+    });
+    let fields = args.fields.iter().map(|Field { key, value }| {
+        let value = match value {
+            FieldValue::Arg(ident) => quote!(std::format!("{:?}", #ident)),
+            FieldValue::Value(expr) => quote!(#expr),
+            FieldValue::Display(expr) => quote!(std::format!("{}", #expr)),
+            FieldValue::Debug(expr) => quote!(std::format!("{:?}", #expr)),
+        };
+        quote!(
+            (std::borrow::Cow::from(#key), #crate_path::collector::Value::from(#value))
+        )
+    });
+    let properties = Punctuated::<_, Token![,]>::from_iter(recorded_args.chain(fields));
     quote!(
         .with_properties(|| [ #properties ])
     )
 }
 
+/// Returns the names bound by the arguments of the function, except `self`.
+fn arg_idents(sig: &Signature) -> Vec<Ident> {
+    fn collect(pat: &Pat, idents: &mut Vec<Ident>) {
+        match pat {
+            Pat::Ident(pat) => idents.push(pat.ident.clone()),
+            Pat::Reference(pat) => collect(&pat.pat, idents),
+            Pat::Type(pat) => collect(&pat.pat, idents),
+            Pat::Tuple(pat) => pat.elems.iter().for_each(|pat| collect(pat, idents)),
+            Pat::TupleStruct(pat) => pat.elems.iter().for_each(|pat| collect(pat, idents)),
+            Pat::Struct(pat) => pat
+                .fields
+                .iter()
+                .for_each(|field| collect(&field.pat, idents)),
+            _ => {}
+        }
+    }
+
+    let mut idents = Vec::new();
+    for input in &sig.inputs {
+        if let FnArg::Typed(pat_type) = input {
+            collect(&pat_type.pat, &mut idents);
+        }
+    }
+    idents
+}
+
+/// Returns the condition for the span to be created, checking the filter and the level decision
+/// cached by the callsite, and then the `if` condition.
+fn gen_enabled(args: &Args, name: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let crate_path = &args.crate_path;
+    let level = match &args.level {
+        Some(level) => {
+            let level = Ident::new(&level.variant, level.lit.span());
+            quote!(Some(#crate_path::Level::#level))
+        }
+        None => quote!(None),
+    };
+    let callsite = quote!(
+        {
+            static __CALLSITE__: #crate_path::Callsite =
+                #crate_path::Callsite::new(module_path!(), #level);
+            __CALLSITE__.enabled(#name)
+        }
+    );
+    match &args.cond {
+        Some(cond) => quote!(#callsite && (#cond)),
+        None => callsite,
+    }
+}
+
+/// Records the return value `__ret__` on the current local span.
+fn gen_record_ret(args: &Args) -> proc_macro2::TokenStream {
+    let crate_path = &args.crate_path;
+    let record_ok = |value: &Ident| match args.ret {
+        Some(format) => {
+            let value = format.format(value);
+            quote!(#crate_path::local::LocalSpan::add_property(|| ("return", #value)))
+        }
+        None => quote!({}),
+    };
+
+    match args.err {
+        Some(format) => {
+            let ok = record_ok(&parse_quote!(__ok__));
+            let err = format.format(&parse_quote!(__err__));
+            quote!(
+                match &__ret__ {
+                    Ok(__ok__) => #ok,
+                    Err(__err__) => #crate_path::local::LocalSpan::set_status(
+                        #crate_path::collector::Status::Error(std::borrow::Cow::from(#err)),
+                    ),
+                }
+            )
+        }
+        None if args.ret.is_some() => {
+            let ret = record_ok(&parse_quote!(__ret__));
+            quote!(#ret;)
+        }
+        None => quote!(),
+    }
+}
+
 /// Instrument a block
 fn gen_block(
     func_name: &Ident,
@@ -348,36 +804,92 @@ fn gen_block(
     async_context: bool,
     async_keyword: bool,
     args: &Args,
+    arg_idents: &[Ident],
     output_ty: Option<Type>,
 ) -> proc_macro2::TokenStream {
     let name = gen_name(func_name, args);
-    let properties = gen_properties(args);
+    let properties = gen_properties(args, arg_idents);
     let crate_path = &args.crate_path;
     let output_ty_hint = output_ty
         .map(erase_impl_trait)
         .unwrap_or_else(|| parse_quote! { _ });
+    let record_ret = args.ret.is_some() || args.err.is_some();
+    let record = gen_record_ret(args);
+
+    // The body is run in an inner async block or closure, where a `return` or `?` returns from
+    // the block instead of the function, so that it does not skip recording the return value.
+    // The closure is called through `call_once()` so that it may return references to the
+    // arguments it captures.
+    let inner_block = if async_context {
+        quote!(async move #block.await)
+    } else {
+        quote!(#crate_path::util::call_once(move || -> #output_ty_hint #block))
+    };
+
+    // The condition is evaluated once per call, and the span is replaced by a noop one if it does
+    // not hold.
+    let enabled = gen_enabled(args, &name);
+    let enabled = quote!(let __enabled__ = #enabled;);
+    let gated_record = if record_ret {
+        quote!(if __enabled__ { #record })
+    } else {
+        quote!()
+    };
 
     // Generate the instrumented function body.
     // If the function is an `async fn`, this will wrap it in an async block.
     // Otherwise, this will enter the span and then perform the rest of the body.
     if async_context {
+        let body = |record: &proc_macro2::TokenStream| {
+            if record_ret {
+                quote!(
+                    let __ret__: #output_ty_hint = #inner_block;
+                    #[allow(unreachable_code)]
+                    {
+                        #record
+                        __ret__
+                    }
+                )
+            } else {
+                quote!(
+                    let __ret__: #output_ty_hint = #block;
+                    #[allow(unreachable_code)]
+                    __ret__
+                )
+            }
+        };
         let block = if args.enter_on_poll {
+            if args.level.is_some() || args.cond.is_some() {
+                abort_call_site!("`enter_on_poll` can not be used with `level` or `if`")
+            }
+            // The span is not filtered, so the return value is always recorded.
+            let block = if record_ret {
+                let body = body(&record);
+                quote!({ #body })
+            } else {
+                quote!(#block)
+            };
             quote!(
                 #crate_path::future::FutureExt::enter_on_poll(
-                    async move { #block },
+                    async move #block,
                     #name
                 )
             )
         } else {
+            let body = body(&gated_record);
+            let span = quote!(
+                if __enabled__ {
+                    #crate_path::Span::enter_with_local_parent( #name ) #properties
+                } else {
+                    #crate_path::Span::noop()
+                }
+            );
             quote!(
                 {
-                    let __span__ = #crate_path::Span::enter_with_local_parent( #name ) #properties;
+                    #enabled
+                    let __span__ = #span;
                     #crate_path::future::FutureExt::in_span(
-                        async move {
-                            let __ret__: #output_ty_hint = #block;
-                            #[allow(unreachable_code)]
-                            __ret__
-                        },
+                        async move { #body },
                         __span__,
                     )
                 }
@@ -396,10 +908,32 @@ fn gen_block(
             abort_call_site!("`enter_on_poll` can not be applied on non-async function");
         }
 
-        quote!(
-            let __guard__ = #crate_path::local::LocalSpan::enter_with_local_parent( #name ) #properties;
-            #block
-        )
+        let guard = quote!(
+            if __enabled__ {
+                #crate_path::local::LocalSpan::enter_with_local_parent( #name ) #properties
+            } else {
+                #crate_path::local::LocalSpan::default()
+            }
+        );
+
+        if record_ret {
+            quote!(
+                #enabled
+                let __guard__ = #guard;
+                let __ret__: #output_ty_hint = #inner_block;
+                #[allow(unreachable_code)]
+                {
+                    #gated_record
+                    __ret__
+                }
+            )
+        } else {
+            quote!(
+                #enabled
+                let __guard__ = #guard;
+                #block
+            )
+        }
     }
 }
 
@@ -519,6 +1053,51 @@ fn get_async_trait_info(block: &Block, block_is_async: bool) -> Option<AsyncTrai
     })
 }
 
+// Get the output type of the future returned by a function expanded by async-trait, from its
+// `Pin<Box<dyn Future<Output = T> + Send + 'async_trait>>` return type.
+fn get_async_trait_output(output: &ReturnType) -> Option<Type> {
+    fn last_generic_type(ty: &Type, ident: &str) -> Option<Type> {
+        let Type::Path(TypePath { path, .. }) = ty else {
+            return None;
+        };
+        let segment = path.segments.last()?;
+        if segment.ident != ident {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        })
+    }
+
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let boxed = last_generic_type(ty, "Pin")?;
+    let Type::TraitObject(future) = last_generic_type(&boxed, "Box")? else {
+        return None;
+    };
+    future.bounds.iter().find_map(|bound| {
+        let TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let segment = bound.path.segments.last()?;
+        if segment.ident != "Future" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(assoc) if assoc.ident == "Output" => Some(assoc.ty.clone()),
+            _ => None,
+        })
+    })
+}
+
 // Return a path as a String
 fn path_to_string(path: &Path) -> String {
     use std::fmt::Write;
@@ -27,12 +27,18 @@ use std::sync::LazyLock;
 use std::time::Duration;
 use std::time::SystemTime;
 
+use fastrace::collector::AsyncReporter;
 use fastrace::collector::EventRecord;
+use fastrace::collector::LinkRecord;
 use fastrace::collector::Reporter;
+use fastrace::collector::TraceContext;
+use fastrace::collector::Value;
 use fastrace::prelude::*;
+use opentelemetry::Array;
 use opentelemetry::InstrumentationScope;
 use opentelemetry::KeyValue;
 use opentelemetry::trace::Event;
+use opentelemetry::trace::Link;
 use opentelemetry::trace::SpanContext as OtelSpanContext;
 use opentelemetry::trace::SpanKind;
 use opentelemetry::trace::Status;
@@ -52,18 +58,24 @@ use opentelemetry_sdk::trace::SpanLinks;
 ///
 /// ## Span Kind
 ///
-/// The reporter automatically maps the `span.kind` property from fastrace spans to OpenTelemetry
-/// span kinds. Supported values are: "client", "server", "producer", "consumer", and "internal"
-/// (case-insensitive). If no `span.kind` property is provided, spans default to
+/// The reporter maps the kind set by `Span::with_kind()` or `LocalSpan::with_kind()` to
+/// OpenTelemetry span kinds.
+///
+/// If the kind is left as `SpanKind::Internal`, the reporter maps the `span.kind` property from
+/// fastrace spans instead. Supported values are: "client", "server", "producer", "consumer", and
+/// "internal" (case-insensitive). If no `span.kind` property is provided, spans default to
 /// `SpanKind::Internal`.
 ///
 /// ## Span Status
 ///
-/// The reporter maps the `span.status_code` and `span.status_description` properties from fastrace
-/// spans to OpenTelemetry span status. Supported codes are: "unset", "ok", and "error"
-/// (case-insensitive). If no `span.status_code` property is provided, spans default to
-/// `Status::Unset`. If the code is "error", the `span.status_description` property is used as the
-/// error description.
+/// The reporter maps the status set by `Span::set_status()` or `LocalSpan::set_status()` to
+/// OpenTelemetry span status.
+///
+/// If the status is not set, the reporter maps the `span.status_code` and
+/// `span.status_description` properties from fastrace spans instead. Supported codes are:
+/// "unset", "ok", and "error" (case-insensitive). If no `span.status_code` property is provided,
+/// spans default to `Status::Unset`. If the code is "error", the `span.status_description`
+/// property is used as the error description.
 ///
 /// ## Parent Span Is Remote
 ///
@@ -108,7 +120,11 @@ pub struct OpenTelemetryReporter {
 /// // treat the fastrace span as its parent.
 /// ```
 pub fn current_opentelemetry_context() -> Option<OtelSpanContext> {
-    let span_context = fastrace::collector::SpanContext::current_local_parent()?;
+    let TraceContext {
+        span_context,
+        trace_state,
+        ..
+    } = TraceContext::current_local_parent()?;
 
     let trace_flags = if span_context.sampled {
         TraceFlags::SAMPLED
@@ -121,7 +137,7 @@ pub fn current_opentelemetry_context() -> Option<OtelSpanContext> {
         span_context.span_id.0.into(),
         trace_flags,
         false,
-        TraceState::default(),
+        map_trace_state(&trace_state),
     ))
 }
 
@@ -140,14 +156,73 @@ static OTEL_PROPERTIES: LazyLock<HashSet<&str>> = LazyLock::new(|| {
 });
 
 /// Convert a list of properties to a list of key-value pairs.
-fn map_props_to_kvs(props: Vec<(Cow<'static, str>, Cow<'static, str>)>) -> Vec<KeyValue> {
+fn map_props_to_kvs(props: Vec<(Cow<'static, str>, Value)>) -> Vec<KeyValue> {
     props
         .into_iter()
         .filter(|(k, _)| !OTEL_PROPERTIES.contains(k.as_ref()))
-        .map(|(k, v)| KeyValue::new(k, v))
+        .map(|(k, v)| KeyValue::new(k, map_value(v)))
         .collect()
 }
 
+/// Convert a property value to an OpenTelemetry attribute value.
+///
+/// Bytes and arrays of mixed types are not supported by OpenTelemetry attributes, so they are
+/// formatted as strings.
+fn map_value(value: Value) -> opentelemetry::Value {
+    match value {
+        Value::Str(s) => opentelemetry::Value::String(s.into()),
+        Value::I64(v) => opentelemetry::Value::I64(v),
+        Value::F64(v) => opentelemetry::Value::F64(v),
+        Value::Bool(v) => opentelemetry::Value::Bool(v),
+        Value::Array(values) => opentelemetry::Value::Array(map_array(values)),
+        value => opentelemetry::Value::String(value.to_string().into()),
+    }
+}
+
+fn map_array(values: Vec<Value>) -> Array {
+    if values.iter().all(|v| matches!(v, Value::Bool(_))) {
+        Array::Bool(
+            values
+                .into_iter()
+                .filter_map(|v| match v {
+                    Value::Bool(v) => Some(v),
+                    _ => None,
+                })
+                .collect(),
+        )
+    } else if values.iter().all(|v| matches!(v, Value::I64(_))) {
+        Array::I64(
+            values
+                .into_iter()
+                .filter_map(|v| match v {
+                    Value::I64(v) => Some(v),
+                    _ => None,
+                })
+                .collect(),
+        )
+    } else if values.iter().all(|v| matches!(v, Value::F64(_))) {
+        Array::F64(
+            values
+                .into_iter()
+                .filter_map(|v| match v {
+                    Value::F64(v) => Some(v),
+                    _ => None,
+                })
+                .collect(),
+        )
+    } else {
+        Array::String(
+            values
+                .into_iter()
+                .map(|v| match v {
+                    Value::Str(s) => s.into(),
+                    v => v.to_string().into(),
+                })
+                .collect(),
+        )
+    }
+}
+
 /// Convert a list of [`EventRecord`] to OpenTelemetry [`SpanEvents`].
 fn map_events(events: Vec<EventRecord>) -> SpanEvents {
     let mut queue = SpanEvents::default();
@@ -167,11 +242,55 @@ fn map_events(events: Vec<EventRecord>) -> SpanEvents {
     queue
 }
 
+/// Convert a list of [`LinkRecord`] to OpenTelemetry [`SpanLinks`].
+fn map_links(links: Vec<LinkRecord>) -> SpanLinks {
+    let mut queue = SpanLinks::default();
+    queue.links.reserve(links.len());
+
+    for LinkRecord {
+        trace_id,
+        span_id,
+        sampled,
+        properties,
+    } in links
+    {
+        let trace_flags = if sampled {
+            TraceFlags::SAMPLED
+        } else {
+            TraceFlags::default()
+        };
+        let span_context = OtelSpanContext::new(
+            trace_id.0.into(),
+            span_id.0.into(),
+            trace_flags,
+            false,
+            TraceState::default(),
+        );
+        let attributes = map_props_to_kvs(properties);
+        queue.links.push(Link::new(span_context, attributes, 0));
+    }
+
+    queue
+}
+
+fn map_trace_state(trace_state: &fastrace::collector::TraceState) -> TraceState {
+    if trace_state.is_empty() {
+        return TraceState::default();
+    }
+
+    TraceState::from_key_value(trace_state.iter()).unwrap_or_else(|err| {
+        log::warn!("failed to convert trace state: {err}");
+        TraceState::default()
+    })
+}
+
 trait DynSpanExporter: Send + Sync + Debug {
     fn export(
         &self,
         batch: Vec<SpanData>,
     ) -> Pin<Box<dyn Future<Output = OTelSdkResult> + Send + '_>>;
+
+    fn shutdown(&mut self) -> OTelSdkResult;
 }
 
 impl<T: SpanExporter> DynSpanExporter for T {
@@ -181,6 +300,10 @@ impl<T: SpanExporter> DynSpanExporter for T {
     ) -> Pin<Box<dyn Future<Output = OTelSdkResult> + Send + '_>> {
         Box::pin(SpanExporter::export(self, batch))
     }
+
+    fn shutdown(&mut self) -> OTelSdkResult {
+        SpanExporter::shutdown(self)
+    }
 }
 
 impl OpenTelemetryReporter {
@@ -209,18 +332,34 @@ impl OpenTelemetryReporter {
                      name,
                      properties,
                      events,
+                     links,
+                     status,
+                     kind,
+                     trace_state,
+                     thread_id,
+                     thread_name,
                  }| {
                     let parent_span_id = parent_id.0.into();
-                    let span_kind = span_kind(&properties);
-                    let status = span_status(&properties);
+                    let span_kind = map_kind(kind, &properties);
+                    let status = map_status(status, &properties);
                     let parent_span_is_remote = parent_span_is_remote(&properties);
                     let instrumentation_scope = self.instrumentation_scope.clone();
                     let start_time =
                         SystemTime::UNIX_EPOCH + Duration::from_nanos(begin_time_unix_ns);
                     let end_time = SystemTime::UNIX_EPOCH
                         + Duration::from_nanos(begin_time_unix_ns + duration_ns);
-                    let attributes = map_props_to_kvs(properties);
+                    let mut attributes = map_props_to_kvs(properties);
+                    if let Some(thread_id) = thread_id {
+                        attributes.push(KeyValue::new("thread.id", thread_id as i64));
+                    }
+                    if let Some(thread_name) = thread_name {
+                        attributes.push(KeyValue::new(
+                            "thread.name",
+                            opentelemetry::StringValue::from(thread_name),
+                        ));
+                    }
                     let events = map_events(events);
+                    let links = map_links(links);
 
                     SpanData {
                         span_context: OtelSpanContext::new(
@@ -228,7 +367,7 @@ impl OpenTelemetryReporter {
                             span_id.0.into(),
                             TraceFlags::default(),
                             parent_span_is_remote,
-                            TraceState::default(),
+                            map_trace_state(&trace_state),
                         ),
                         parent_span_id,
                         parent_span_is_remote,
@@ -239,7 +378,7 @@ impl OpenTelemetryReporter {
                         attributes,
                         dropped_attributes_count: 0,
                         events,
-                        links: SpanLinks::default(),
+                        links,
                         status,
                         instrumentation_scope,
                     }
@@ -265,13 +404,54 @@ impl Reporter for OpenTelemetryReporter {
             log::error!("failed to report to opentelemetry: {err}");
         }
     }
+
+    fn shutdown(&mut self) {
+        if let Err(err) = self.exporter.shutdown() {
+            log::error!("failed to shutdown opentelemetry exporter: {err}");
+        }
+    }
 }
 
-fn span_kind(properties: &[(Cow<'static, str>, Cow<'static, str>)]) -> SpanKind {
+/// Exports spans without blocking the current thread. Use it together with
+/// [`AsyncReporterAdapter`](fastrace::collector::AsyncReporterAdapter) to keep a slow exporter
+/// from delaying the global collector.
+impl AsyncReporter for OpenTelemetryReporter {
+    async fn report_async(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        let spans = self.convert(spans);
+        if let Err(err) = self.exporter.export(spans).await {
+            log::error!("failed to report to opentelemetry: {err}");
+        }
+    }
+
+    async fn shutdown_async(&mut self) {
+        if let Err(err) = self.exporter.shutdown() {
+            log::error!("failed to shutdown opentelemetry exporter: {err}");
+        }
+    }
+}
+
+fn map_kind(
+    kind: fastrace::collector::SpanKind,
+    properties: &[(Cow<'static, str>, Value)],
+) -> SpanKind {
+    match kind {
+        fastrace::collector::SpanKind::Server => SpanKind::Server,
+        fastrace::collector::SpanKind::Client => SpanKind::Client,
+        fastrace::collector::SpanKind::Producer => SpanKind::Producer,
+        fastrace::collector::SpanKind::Consumer => SpanKind::Consumer,
+        fastrace::collector::SpanKind::Internal => span_kind(properties),
+    }
+}
+
+fn span_kind(properties: &[(Cow<'static, str>, Value)]) -> SpanKind {
     properties
         .iter()
         .find(|(k, _)| k == SPAN_KIND)
-        .and_then(|(_, v)| match v.to_lowercase().as_str() {
+        .and_then(|(_, v)| match v.as_str()?.to_lowercase().as_str() {
             "client" => Some(SpanKind::Client),
             "server" => Some(SpanKind::Server),
             "producer" => Some(SpanKind::Producer),
@@ -282,7 +462,18 @@ fn span_kind(properties: &[(Cow<'static, str>, Cow<'static, str>)]) -> SpanKind
         .unwrap_or(SpanKind::Internal)
 }
 
-fn span_status(properties: &[(Cow<'static, str>, Cow<'static, str>)]) -> Status {
+fn map_status(
+    status: fastrace::collector::Status,
+    properties: &[(Cow<'static, str>, Value)],
+) -> Status {
+    match status {
+        fastrace::collector::Status::Unset => span_status(properties),
+        fastrace::collector::Status::Ok => Status::Ok,
+        fastrace::collector::Status::Error(description) => Status::Error { description },
+    }
+}
+
+fn span_status(properties: &[(Cow<'static, str>, Value)]) -> Status {
     let status_description = properties
         .iter()
         .find(|(k, _)| k == SPAN_STATUS_DESCRIPTION)
@@ -291,7 +482,7 @@ fn span_status(properties: &[(Cow<'static, str>, Cow<'static, str>)]) -> Status
     properties
         .iter()
         .find(|(k, _)| k == SPAN_STATUS_CODE)
-        .and_then(|(_, v)| match v.to_lowercase().as_str() {
+        .and_then(|(_, v)| match v.as_str()?.to_lowercase().as_str() {
             "unset" => Some(Status::Unset),
             "ok" => Some(Status::Ok),
             "error" => Some(Status::Error {
@@ -302,10 +493,13 @@ fn span_status(properties: &[(Cow<'static, str>, Cow<'static, str>)]) -> Status
         .unwrap_or(Status::Unset)
 }
 
-fn parent_span_is_remote(properties: &[(Cow<'static, str>, Cow<'static, str>)]) -> bool {
+fn parent_span_is_remote(properties: &[(Cow<'static, str>, Value)]) -> bool {
     properties
         .iter()
         .find(|(k, _)| k == SPAN_PARENT_SPAN_IS_REMOTE)
-        .map(|(_, v)| v.to_lowercase().as_str() == "true")
+        .map(|(_, v)| match v {
+            Value::Bool(v) => *v,
+            v => v.as_str().is_some_and(|v| v.eq_ignore_ascii_case("true")),
+        })
         .unwrap_or(false)
 }
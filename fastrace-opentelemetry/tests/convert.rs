@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use fastrace::collector::AsyncReporter;
+use fastrace::collector::AsyncReporterAdapter;
+use fastrace::collector::LinkRecord;
+use fastrace::collector::Reporter;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
+use fastrace::collector::TraceState;
+use fastrace::collector::Value;
+use fastrace::prelude::*;
+use fastrace_opentelemetry::OpenTelemetryReporter;
+use opentelemetry::Array;
+use opentelemetry::InstrumentationScope;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::trace::SpanData;
+use opentelemetry_sdk::trace::SpanExporter;
+
+#[derive(Debug, Clone, Default)]
+struct CapturingExporter {
+    spans: Arc<Mutex<Vec<SpanData>>>,
+}
+
+impl SpanExporter for CapturingExporter {
+    fn export(
+        &self,
+        batch: Vec<SpanData>,
+    ) -> impl std::future::Future<Output = OTelSdkResult> + Send {
+        self.spans.lock().unwrap().extend(batch);
+        std::future::ready(Ok(()))
+    }
+}
+
+fn report(record: SpanRecord) -> SpanData {
+    let exporter = CapturingExporter::default();
+    let spans = exporter.spans.clone();
+    let mut reporter = OpenTelemetryReporter::new(
+        exporter,
+        Cow::Owned(Resource::builder_empty().build()),
+        InstrumentationScope::builder("fastrace-opentelemetry-test").build(),
+    );
+    reporter.report(vec![record]);
+
+    let mut spans = spans.lock().unwrap();
+    assert_eq!(spans.len(), 1);
+    spans.pop().unwrap()
+}
+
+#[test]
+fn links_are_exported() {
+    let span = report(SpanRecord {
+        trace_id: TraceId(1),
+        span_id: SpanId(2),
+        name: "consume".into(),
+        links: vec![LinkRecord {
+            trace_id: TraceId(3),
+            span_id: SpanId(4),
+            sampled: true,
+            properties: vec![("messaging.message.id".into(), "42".into())],
+        }],
+        ..SpanRecord::default()
+    });
+
+    assert_eq!(span.links.links.len(), 1);
+    let link = &span.links.links[0];
+    assert_eq!(link.span_context.trace_id(), 3u128.into());
+    assert_eq!(link.span_context.span_id(), 4u64.into());
+    assert!(link.span_context.is_sampled());
+    assert!(!link.span_context.is_remote());
+    assert_eq!(link.attributes, vec![KeyValue::new(
+        "messaging.message.id",
+        "42"
+    )]);
+}
+
+#[test]
+fn status_is_exported() {
+    let span = report(SpanRecord {
+        status: Status::Error("failed".into()),
+        ..SpanRecord::default()
+    });
+    assert_eq!(span.status, opentelemetry::trace::Status::error("failed"));
+
+    let span = report(SpanRecord {
+        properties: vec![(fastrace_opentelemetry::SPAN_STATUS_CODE.into(), "ok".into())],
+        ..SpanRecord::default()
+    });
+    assert_eq!(span.status, opentelemetry::trace::Status::Ok);
+}
+
+#[test]
+fn kind_is_exported() {
+    let span = report(SpanRecord {
+        kind: SpanKind::Consumer,
+        properties: vec![(fastrace_opentelemetry::SPAN_KIND.into(), "server".into())],
+        ..SpanRecord::default()
+    });
+    assert_eq!(span.span_kind, opentelemetry::trace::SpanKind::Consumer);
+
+    let span = report(SpanRecord {
+        properties: vec![(fastrace_opentelemetry::SPAN_KIND.into(), "server".into())],
+        ..SpanRecord::default()
+    });
+    assert_eq!(span.span_kind, opentelemetry::trace::SpanKind::Server);
+}
+
+#[test]
+fn trace_state_is_exported() {
+    let span = report(SpanRecord {
+        trace_state: TraceState::decode_w3c_tracestate("congo=t61rcWkgMzE,rojo=00f067aa0ba902b7"),
+        ..SpanRecord::default()
+    });
+    assert_eq!(
+        span.span_context.trace_state().header(),
+        "congo=t61rcWkgMzE,rojo=00f067aa0ba902b7"
+    );
+}
+
+#[test]
+fn typed_properties_are_exported() {
+    let span = report(SpanRecord {
+        properties: vec![
+            ("str".into(), Value::Str("v".into())),
+            ("i64".into(), Value::I64(-1)),
+            ("f64".into(), Value::F64(0.5)),
+            ("bool".into(), Value::Bool(true)),
+            ("bytes".into(), Value::Bytes(vec![0, 255].into())),
+            (
+                "array".into(),
+                Value::Array(vec![Value::I64(1), Value::I64(2)]),
+            ),
+            (
+                "mixed".into(),
+                Value::Array(vec![Value::I64(1), Value::Str("b".into())]),
+            ),
+        ],
+        ..SpanRecord::default()
+    });
+
+    assert_eq!(span.attributes, vec![
+        KeyValue::new("str", "v"),
+        KeyValue::new("i64", -1),
+        KeyValue::new("f64", 0.5),
+        KeyValue::new("bool", true),
+        KeyValue::new("bytes", "00ff"),
+        KeyValue::new("array", opentelemetry::Value::Array(Array::I64(vec![1, 2]))),
+        KeyValue::new(
+            "mixed",
+            opentelemetry::Value::Array(Array::String(vec!["1".into(), "b".into()])),
+        ),
+    ]);
+}
+
+#[test]
+fn thread_is_exported() {
+    let span = report(SpanRecord {
+        properties: vec![("k".into(), "v".into())],
+        thread_id: Some(7),
+        thread_name: Some("worker".into()),
+        ..SpanRecord::default()
+    });
+    assert_eq!(span.attributes, vec![
+        KeyValue::new("k", "v"),
+        KeyValue::new("thread.id", 7),
+        KeyValue::new("thread.name", "worker"),
+    ]);
+
+    let span = report(SpanRecord::default());
+    assert!(span.attributes.is_empty());
+}
+
+#[test]
+fn async_reporter_exports() {
+    let exporter = CapturingExporter::default();
+    let spans = exporter.spans.clone();
+    let mut reporter = OpenTelemetryReporter::new(
+        exporter,
+        Cow::Owned(Resource::builder_empty().build()),
+        InstrumentationScope::builder("fastrace-opentelemetry-test").build(),
+    );
+    pollster::block_on(reporter.report_async(vec![SpanRecord::default()]));
+    assert_eq!(spans.lock().unwrap().len(), 1);
+
+    let mut reporter = AsyncReporterAdapter::new(reporter, 1);
+    reporter.report(vec![SpanRecord::default()]);
+    reporter.shutdown();
+    assert_eq!(spans.lock().unwrap().len(), 2);
+}
@@ -0,0 +1,273 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![doc = include_str!("../README.md")]
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+
+use fastrace::Event;
+use fastrace::Span;
+use fastrace::collector::SpanContext;
+use fastrace::collector::Value;
+use fastrace::local::LocalParentGuard;
+use fastrace::local::LocalSpan;
+use tracing_core::Subscriber;
+use tracing_core::field::Field;
+use tracing_core::field::Visit;
+use tracing_core::span;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+thread_local! {
+    /// The local parent guards of the `tracing` spans entered on this thread, innermost last,
+    /// along with whether the span has exited.
+    ///
+    /// `tracing` allows spans to exit out of order, while local parent guards must be dropped in
+    /// the reverse order they were created. The guard of an exited span is therefore kept until
+    /// every guard created after it is released.
+    static ENTERED: RefCell<Vec<(span::Id, LocalParentGuard, bool)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A [`Layer`] that reports `tracing` spans and events to fastrace.
+///
+/// - A `tracing` span becomes a fastrace [`Span`] named after it, under the current [local
+///   parent](SpanContext::current_local_parent), or under its explicit `tracing` parent. Like
+///   [`Span::enter_with_local_parent()`], no span is recorded if there is no parent, so `tracing`
+///   spans only show up inside fastrace traces.
+/// - While a `tracing` span is entered, its fastrace span is the local parent of the thread, so
+///   that the spans created inside, either by `tracing` or by fastrace, become its children.
+/// - The fields of a `tracing` span, including those recorded later, become its properties.
+/// - A `tracing` event becomes an [`Event`] of the current local parent, named after its message,
+///   with its fields, `level` and `target` as properties.
+/// - A span following from another one is [linked](Span::add_link) to it.
+///
+/// The span is reported when the `tracing` span is closed.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::Config;
+/// use fastrace::collector::ConsoleReporter;
+/// use fastrace::prelude::*;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let subscriber =
+///     tracing_subscriber::Registry::default().with(fastrace_tracing_bridge::FastraceLayer::new());
+/// tracing::subscriber::set_global_default(subscriber).unwrap();
+/// fastrace::set_reporter(ConsoleReporter, Config::default());
+///
+/// {
+///     let root = Span::root("root", SpanContext::random());
+///     let _guard = root.set_local_parent();
+///
+///     let _span = tracing::info_span!("query", table = "users").entered();
+///     tracing::info!(rows = 3, "fetched");
+/// }
+///
+/// fastrace::flush();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FastraceLayer {
+    _private: (),
+}
+
+impl FastraceLayer {
+    /// Creates a `FastraceLayer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for FastraceLayer
+where S: Subscriber + for<'a> LookupSpan<'a>
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span_ref) = ctx.span(id) else {
+            return;
+        };
+
+        let name = attrs.metadata().name();
+        let span = match attrs.parent() {
+            Some(parent) => ctx
+                .span(parent)
+                .and_then(|parent| {
+                    let extensions = parent.extensions();
+                    let parent = extensions.get::<Span>()?;
+                    Some(Span::enter_with_parent(name, parent))
+                })
+                .unwrap_or_else(Span::noop),
+            None => Span::enter_with_local_parent(name),
+        };
+        let span = span.with_properties(|| {
+            let mut visitor = Properties::default();
+            attrs.record(&mut visitor);
+            visitor.properties
+        });
+
+        span_ref.extensions_mut().insert(span);
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let Some(span_ref) = ctx.span(id) else {
+            return;
+        };
+
+        let extensions = span_ref.extensions();
+        if let Some(span) = extensions.get::<Span>() {
+            span.add_properties(|| {
+                let mut visitor = Properties::default();
+                values.record(&mut visitor);
+                visitor.properties
+            });
+        }
+    }
+
+    fn on_follows_from(&self, id: &span::Id, follows: &span::Id, ctx: Context<'_, S>) {
+        let Some(context) = ctx
+            .span(follows)
+            .and_then(|follows| SpanContext::from_span(follows.extensions().get::<Span>()?))
+        else {
+            return;
+        };
+
+        if let Some(span_ref) = ctx.span(id) {
+            if let Some(span) = span_ref.extensions().get::<Span>() {
+                span.add_link(context, Vec::<(&'static str, Value)>::new);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &tracing_core::Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = Properties {
+            event: true,
+            message: None,
+            properties: vec![
+                (Cow::Borrowed("level"), metadata.level().as_str().into()),
+                (Cow::Borrowed("target"), metadata.target().into()),
+            ],
+        };
+        event.record(&mut visitor);
+
+        let name = match visitor.message {
+            Some(message) => Cow::Owned(message),
+            None => Cow::Borrowed(metadata.name()),
+        };
+        let fastrace_event = Event::new(name).with_properties(|| visitor.properties);
+
+        match event.parent() {
+            Some(parent) => {
+                if let Some(span_ref) = ctx.span(parent) {
+                    if let Some(span) = span_ref.extensions().get::<Span>() {
+                        span.add_event(fastrace_event);
+                    }
+                }
+            }
+            None => LocalSpan::add_event(fastrace_event),
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span_ref) = ctx.span(id) else {
+            return;
+        };
+
+        let guard = match span_ref.extensions().get::<Span>() {
+            Some(span) => span.set_local_parent(),
+            None => return,
+        };
+        let _ = ENTERED.try_with(|entered| entered.borrow_mut().push((id.clone(), guard, false)));
+    }
+
+    fn on_exit(&self, id: &span::Id, _ctx: Context<'_, S>) {
+        let _ = ENTERED.try_with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some((_, _, exited)) = entered
+                .iter_mut()
+                .rev()
+                .find(|(entered_id, _, exited)| entered_id == id && !*exited)
+            {
+                *exited = true;
+            }
+            while entered.last().is_some_and(|(_, _, exited)| *exited) {
+                entered.pop();
+            }
+        });
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        if let Some(span_ref) = ctx.span(&id) {
+            span_ref.extensions_mut().remove::<Span>();
+        }
+    }
+}
+
+/// Collects the fields of a span or an event as properties.
+#[derive(Default)]
+struct Properties {
+    /// Whether the fields are those of an event, whose `message` field names the event instead
+    /// of being recorded as a property.
+    event: bool,
+    message: Option<String>,
+    properties: Vec<(Cow<'static, str>, Value)>,
+}
+
+impl Properties {
+    fn record(&mut self, field: &Field, value: impl Into<Value>) {
+        let value = value.into();
+        if self.event && field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.properties.push((Cow::Borrowed(field.name()), value));
+        }
+    }
+}
+
+impl Visit for Properties {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, value);
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_bytes(&mut self, field: &Field, value: &[u8]) {
+        self.record(field, value.to_vec());
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn Error + 'static)) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{value:?}"));
+    }
+}
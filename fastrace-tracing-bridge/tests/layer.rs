@@ -0,0 +1,192 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Once;
+
+use fastrace::collector::Config;
+use fastrace::collector::TestReporter;
+use fastrace::prelude::*;
+use fastrace::util::tree::tree_str_from_span_records;
+use fastrace_tracing_bridge::FastraceLayer;
+use serial_test::serial;
+use tracing::Instrument;
+use tracing_subscriber::layer::SubscriberExt;
+
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let subscriber = tracing_subscriber::Registry::default().with(FastraceLayer::new());
+        tracing::subscriber::set_global_default(subscriber).unwrap();
+    });
+}
+
+#[test]
+#[serial]
+fn spans_and_events() {
+    init();
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    // Not recorded without a fastrace parent.
+    tracing::info_span!("orphan").in_scope(|| tracing::info!("ignored"));
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+
+        let outer = tracing::info_span!(
+            "outer",
+            id = 7,
+            name = "users",
+            ok = true,
+            extra = tracing::field::Empty
+        );
+        outer.in_scope(|| {
+            tracing::warn!(retries = 2, "slow query");
+
+            let _local = LocalSpan::enter_with_local_parent("local");
+            let _inner = tracing::debug_span!("inner", ratio = 0.5).entered();
+            tracing::info!(name: "done", rows = 3u64);
+        });
+        outer.record("extra", "late");
+
+        let detached = tracing::info_span!(parent: &outer, "detached");
+        tracing::info!(parent: &detached, "explicit parent");
+    }
+
+    fastrace::flush();
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        outer [("id", "7"), ("name", "users"), ("ok", "true"), ("extra", "late")] [("slow query", [("level", "WARN"), ("target", "layer"), ("retries", "2")])]
+            detached [] [("explicit parent", [("level", "INFO"), ("target", "layer")])]
+            local []
+                inner [("ratio", "0.5")] [("done", [("level", "INFO"), ("target", "layer"), ("rows", "3")])]
+    "###);
+}
+
+#[test]
+#[serial]
+fn async_spans() {
+    #[trace(short_name = true)]
+    async fn fetch() {
+        tracing::info!("fetching");
+        tokio::task::yield_now().await;
+    }
+
+    init();
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .unwrap();
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+
+        let handles: Vec<_> = (0..2)
+            .map(|i| {
+                let span = tracing::info_span!("task", i);
+                runtime.spawn(
+                    async {
+                        fetch().await;
+                        tokio::task::yield_now().await;
+                        fetch().await;
+                    }
+                    .instrument(span),
+                )
+            })
+            .collect();
+        runtime.block_on(async {
+            for handle in handles {
+                handle.await.unwrap();
+            }
+        });
+    }
+
+    fastrace::flush();
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        task [("i", "0")]
+            fetch [] [("fetching", [("level", "INFO"), ("target", "layer")])]
+            fetch [] [("fetching", [("level", "INFO"), ("target", "layer")])]
+        task [("i", "1")]
+            fetch [] [("fetching", [("level", "INFO"), ("target", "layer")])]
+            fetch [] [("fetching", [("level", "INFO"), ("target", "layer")])]
+    "###);
+}
+
+#[test]
+#[serial]
+fn follows_from() {
+    init();
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+
+        let producer = tracing::info_span!("producer");
+        let consumer = tracing::info_span!("consumer");
+        consumer.follows_from(&producer);
+    }
+
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    let producer = spans.iter().find(|span| span.name == "producer").unwrap();
+    let consumer = spans.iter().find(|span| span.name == "consumer").unwrap();
+    assert_eq!(consumer.links.len(), 1);
+    assert_eq!(consumer.links[0].trace_id, producer.trace_id);
+    assert_eq!(consumer.links[0].span_id, producer.span_id);
+}
+
+#[test]
+#[serial]
+fn out_of_order_exit() {
+    init();
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+
+        let a = tracing::info_span!("a").entered();
+        let b = tracing::info_span!("b").entered();
+        drop(a);
+        {
+            let _x = LocalSpan::enter_with_local_parent("x");
+        }
+        drop(b);
+    }
+
+    fastrace::flush();
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        a []
+            b []
+                x []
+    "###);
+}
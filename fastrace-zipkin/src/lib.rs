@@ -0,0 +1,169 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![doc = include_str!("../README.md")]
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use fastrace::collector::EventRecord;
+use fastrace::collector::Reporter;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
+use fastrace::collector::Value;
+use fastrace::prelude::*;
+use serde::Serialize;
+
+/// [Zipkin](https://zipkin.io/) reporter for `fastrace` in the v2 JSON format.
+///
+/// Properties are reported as tags, and events as annotations. An errored span carries the
+/// `error` tag, whose value is the error message.
+pub struct ZipkinReporter {
+    client: reqwest::blocking::Client,
+    endpoint: String,
+    service_name: String,
+}
+
+impl ZipkinReporter {
+    /// Creates a reporter that posts spans to `endpoint`, typically
+    /// `http://127.0.0.1:9411/api/v2/spans`.
+    pub fn new(endpoint: impl Into<String>, service_name: impl Into<String>) -> ZipkinReporter {
+        ZipkinReporter {
+            client: reqwest::blocking::Client::new(),
+            endpoint: endpoint.into(),
+            service_name: service_name.into(),
+        }
+    }
+
+    fn convert<'a>(&'a self, spans: &'a [SpanRecord]) -> Vec<ZipkinSpan<'a>> {
+        spans
+            .iter()
+            .map(move |s| {
+                let mut tags: BTreeMap<&str, Cow<str>> = s
+                    .properties
+                    .iter()
+                    .map(|(k, v)| (k.as_ref(), tag_value(v)))
+                    .collect();
+                if let Status::Error(message) = &s.status {
+                    tags.insert("error", Cow::Borrowed(message.as_ref()));
+                }
+
+                ZipkinSpan {
+                    trace_id: s.trace_id.to_string(),
+                    id: s.span_id.to_string(),
+                    parent_id: (s.parent_id.0 != 0).then(|| s.parent_id.to_string()),
+                    name: &s.name,
+                    kind: span_kind(s.kind),
+                    timestamp: s.begin_time_unix_ns / 1_000,
+                    duration: s.duration_ns / 1_000,
+                    local_endpoint: Endpoint {
+                        service_name: &self.service_name,
+                    },
+                    tags,
+                    annotations: s.events.iter().map(annotation).collect(),
+                }
+            })
+            .collect()
+    }
+
+    fn try_report(&self, spans: Vec<SpanRecord>) -> Result<(), Box<dyn std::error::Error>> {
+        let zipkin_spans = self.convert(&spans);
+        let bytes = serde_json::to_vec(&zipkin_spans)?;
+        self.client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/json")
+            .body(bytes)
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+fn span_kind(kind: SpanKind) -> Option<&'static str> {
+    match kind {
+        SpanKind::Server => Some("SERVER"),
+        SpanKind::Client => Some("CLIENT"),
+        SpanKind::Producer => Some("PRODUCER"),
+        SpanKind::Consumer => Some("CONSUMER"),
+        SpanKind::Internal => None,
+    }
+}
+
+fn tag_value(value: &Value) -> Cow<'_, str> {
+    match value {
+        Value::Str(s) => Cow::Borrowed(s),
+        v => Cow::Owned(v.to_string()),
+    }
+}
+
+/// Zipkin annotations are plain strings, so the properties of an event are appended to its
+/// name as `key=value` pairs.
+fn annotation(event: &EventRecord) -> Annotation<'_> {
+    let value = if event.properties.is_empty() {
+        Cow::Borrowed(event.name.as_ref())
+    } else {
+        let mut value = event.name.to_string();
+        for (k, v) in &event.properties {
+            value.push_str(&format!(" {k}={v}"));
+        }
+        Cow::Owned(value)
+    };
+    Annotation {
+        timestamp: event.timestamp_unix_ns / 1_000,
+        value,
+    }
+}
+
+impl Reporter for ZipkinReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        if let Err(err) = self.try_report(spans) {
+            log::error!("report to zipkin failed: {err}");
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ZipkinSpan<'a> {
+    trace_id: String,
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<String>,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'static str>,
+    timestamp: u64,
+    duration: u64,
+    local_endpoint: Endpoint<'a>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    tags: BTreeMap<&'a str, Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Endpoint<'a> {
+    service_name: &'a str,
+}
+
+#[derive(Serialize)]
+struct Annotation<'a> {
+    timestamp: u64,
+    value: Cow<'a, str>,
+}
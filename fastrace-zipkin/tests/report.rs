@@ -0,0 +1,147 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::thread;
+
+use fastrace::collector::EventRecord;
+use fastrace::collector::Reporter;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
+use fastrace::prelude::*;
+use fastrace_zipkin::ZipkinReporter;
+use serde_json::Value;
+use serde_json::json;
+
+struct Request {
+    request_line: String,
+    content_type: Option<String>,
+    body: Value,
+}
+
+/// Reports `spans` to a local HTTP stand-in for the Zipkin collector, and returns the request
+/// it received.
+fn report(spans: Vec<SpanRecord>) -> Request {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut content_type = None;
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':').unwrap();
+            match name.to_ascii_lowercase().as_str() {
+                "content-type" => content_type = Some(value.trim().to_string()),
+                "content-length" => content_length = value.trim().parse().unwrap(),
+                _ => {}
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 202 Accepted\r\ncontent-length: 0\r\n\r\n")
+            .unwrap();
+
+        Request {
+            request_line: request_line.trim_end().to_string(),
+            content_type,
+            body: serde_json::from_slice(&body).unwrap(),
+        }
+    });
+
+    let mut reporter = ZipkinReporter::new(format!("http://{addr}/api/v2/spans"), "test-service");
+    reporter.report(spans);
+
+    server.join().unwrap()
+}
+
+#[test]
+fn spans_are_posted() {
+    let request = report(vec![
+        SpanRecord {
+            trace_id: TraceId(0x0af7651916cd43dd8448eb211c80319c),
+            span_id: SpanId(1),
+            begin_time_unix_ns: 1_000_000,
+            duration_ns: 2_500_000,
+            name: "root".into(),
+            kind: SpanKind::Server,
+            properties: vec![
+                ("http.method".into(), "GET".into()),
+                ("http.status_code".into(), 500i64.into()),
+            ],
+            status: Status::Error("internal error".into()),
+            ..SpanRecord::default()
+        },
+        SpanRecord {
+            trace_id: TraceId(0x0af7651916cd43dd8448eb211c80319c),
+            span_id: SpanId(2),
+            parent_id: SpanId(1),
+            begin_time_unix_ns: 1_500_000,
+            duration_ns: 1_000_000,
+            name: "child".into(),
+            events: vec![
+                EventRecord {
+                    name: "retry".into(),
+                    timestamp_unix_ns: 2_000_000,
+                    properties: vec![("attempt".into(), 2i64.into())],
+                },
+                EventRecord {
+                    name: "done".into(),
+                    timestamp_unix_ns: 2_400_000,
+                    properties: vec![],
+                },
+            ],
+            ..SpanRecord::default()
+        },
+    ]);
+
+    assert_eq!(request.request_line, "POST /api/v2/spans HTTP/1.1");
+    assert_eq!(request.content_type.as_deref(), Some("application/json"));
+    assert_eq!(
+        request.body,
+        json!([
+            {
+                "traceId": "0af7651916cd43dd8448eb211c80319c",
+                "id": "0000000000000001",
+                "name": "root",
+                "kind": "SERVER",
+                "timestamp": 1000,
+                "duration": 2500,
+                "localEndpoint": { "serviceName": "test-service" },
+                "tags": {
+                    "error": "internal error",
+                    "http.method": "GET",
+                    "http.status_code": "500",
+                },
+            },
+            {
+                "traceId": "0af7651916cd43dd8448eb211c80319c",
+                "id": "0000000000000002",
+                "parentId": "0000000000000001",
+                "name": "child",
+                "timestamp": 1500,
+                "duration": 1000,
+                "localEndpoint": { "serviceName": "test-service" },
+                "annotations": [
+                    { "timestamp": 2000, "value": "retry attempt=2" },
+                    { "timestamp": 2400, "value": "done" },
+                ],
+            },
+        ])
+    );
+}
@@ -0,0 +1,253 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
+use std::sync::atomic::Ordering;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::thread::Thread;
+
+use crate::collector::SpanRecord;
+use crate::collector::global_collector::Reporter;
+#[cfg(not(target_family = "wasm"))]
+use crate::collector::stats;
+
+/// The minimum interval between two warnings about dropped batches.
+#[cfg(not(target_family = "wasm"))]
+const DROP_WARNING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// A trait defining the behavior of an asynchronous reporter.
+///
+/// Unlike [`Reporter`], the returned future is not awaited by the global collector. Wrap the
+/// reporter in an [`AsyncReporterAdapter`] to run it on a dedicated thread so that a slow
+/// backend never delays span collection.
+pub trait AsyncReporter: Send + 'static {
+    /// Reports a batch of spans to a remote service.
+    fn report_async(&mut self, spans: Vec<SpanRecord>) -> impl Future<Output = ()>;
+
+    /// Flushes any buffered spans and releases the resources held by the reporter.
+    ///
+    /// Called once after the last batch has been reported.
+    fn shutdown_async(&mut self) -> impl Future<Output = ()> {
+        std::future::ready(())
+    }
+}
+
+/// An adapter that turns an [`AsyncReporter`] into a [`Reporter`].
+///
+/// Batches are handed off to a dedicated `fastrace-async-reporter` thread through a bounded
+/// queue, where the [`AsyncReporter`] is driven to completion one batch at a time. If the queue
+/// is full, the incoming batch is dropped instead of blocking the global collector. Dropped
+/// spans are counted in [`Stats::async_reporter_spans_dropped`], and a warning is logged at most
+/// once every 10 seconds.
+///
+/// [`Stats::async_reporter_spans_dropped`]: crate::collector::Stats::async_reporter_spans_dropped
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::AsyncReporter;
+/// use fastrace::collector::AsyncReporterAdapter;
+/// use fastrace::collector::Config;
+/// use fastrace::prelude::*;
+///
+/// struct MyReporter;
+///
+/// impl AsyncReporter for MyReporter {
+///     async fn report_async(&mut self, spans: Vec<SpanRecord>) {
+///         // Send spans to a remote service...
+///     }
+/// }
+///
+/// fastrace::set_reporter(AsyncReporterAdapter::new(MyReporter, 16), Config::default());
+/// ```
+pub struct AsyncReporterAdapter<R> {
+    #[cfg(not(target_family = "wasm"))]
+    tx: Option<std::sync::mpsc::SyncSender<Vec<SpanRecord>>>,
+    #[cfg(not(target_family = "wasm"))]
+    handle: Option<std::thread::JoinHandle<()>>,
+    /// The number of batches dropped since the last warning.
+    #[cfg(not(target_family = "wasm"))]
+    dropped_batches: usize,
+    #[cfg(not(target_family = "wasm"))]
+    last_warning: Option<std::time::Instant>,
+    #[cfg(target_family = "wasm")]
+    reporter: R,
+    #[cfg(not(target_family = "wasm"))]
+    _marker: std::marker::PhantomData<fn(R)>,
+}
+
+impl<R: AsyncReporter> AsyncReporterAdapter<R> {
+    /// Creates an adapter which hands off at most `capacity` pending batches to the reporter.
+    pub fn new(reporter: R, capacity: usize) -> Self {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let (tx, rx) = std::sync::mpsc::sync_channel::<Vec<SpanRecord>>(capacity);
+            let mut reporter = reporter;
+            let handle = std::thread::Builder::new()
+                .name("fastrace-async-reporter".to_string())
+                .spawn(move || {
+                    while let Ok(spans) = rx.recv() {
+                        block_on(reporter.report_async(spans));
+                    }
+                    block_on(reporter.shutdown_async());
+                })
+                .unwrap();
+
+            Self {
+                tx: Some(tx),
+                handle: Some(handle),
+                dropped_batches: 0,
+                last_warning: None,
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        #[cfg(target_family = "wasm")]
+        {
+            let _ = capacity;
+            Self { reporter }
+        }
+    }
+}
+
+impl<R: AsyncReporter> Reporter for AsyncReporterAdapter<R> {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(tx) = &self.tx {
+            let len = spans.len();
+            if tx.try_send(spans).is_err() {
+                stats::ASYNC_REPORTER_SPANS_DROPPED.fetch_add(len as u64, Ordering::Relaxed);
+                self.dropped_batches += 1;
+                if self
+                    .last_warning
+                    .map_or(true, |last| last.elapsed() >= DROP_WARNING_INTERVAL)
+                {
+                    log::warn!(
+                        "fastrace-async-reporter queue is full, dropped {} batches",
+                        self.dropped_batches
+                    );
+                    self.dropped_batches = 0;
+                    self.last_warning = Some(std::time::Instant::now());
+                }
+            }
+        }
+
+        #[cfg(target_family = "wasm")]
+        block_on(self.reporter.report_async(spans));
+    }
+
+    /// Waits for the queued batches to be reported and then shuts down the [`AsyncReporter`].
+    fn shutdown(&mut self) {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            self.tx.take();
+            if let Some(handle) = self.handle.take() {
+                handle.join().ok();
+            }
+        }
+
+        #[cfg(target_family = "wasm")]
+        block_on(self.reporter.shutdown_async());
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a future to completion on the current thread.
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    struct ChannelReporter(std::sync::mpsc::Sender<Vec<SpanRecord>>);
+
+    impl AsyncReporter for ChannelReporter {
+        async fn report_async(&mut self, spans: Vec<SpanRecord>) {
+            futures_timer::Delay::new(Duration::from_millis(10)).await;
+            self.0.send(spans).unwrap();
+        }
+
+        async fn shutdown_async(&mut self) {
+            self.0.send(vec![]).unwrap();
+        }
+    }
+
+    #[test]
+    fn async_reporter_adapter() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut reporter = AsyncReporterAdapter::new(ChannelReporter(tx), 4);
+
+        reporter.report(vec![SpanRecord::default()]);
+        reporter.report(vec![]);
+        reporter.report(vec![SpanRecord::default(), SpanRecord::default()]);
+
+        reporter.shutdown();
+
+        let received = rx.try_iter().map(|spans| spans.len()).collect::<Vec<_>>();
+        assert_eq!(received, vec![1, 2, 0]);
+    }
+
+    struct BlockingReporter(std::sync::mpsc::Receiver<()>);
+
+    impl AsyncReporter for BlockingReporter {
+        async fn report_async(&mut self, _spans: Vec<SpanRecord>) {
+            self.0.recv().ok();
+        }
+    }
+
+    #[test]
+    fn async_reporter_adapter_full() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut reporter = AsyncReporterAdapter::new(BlockingReporter(rx), 1);
+        let dropped = stats::stats().async_reporter_spans_dropped;
+
+        // At most one batch is being reported and one is queued, so at least two are dropped.
+        for _ in 0..4 {
+            reporter.report(vec![SpanRecord::default(); 2]);
+        }
+        assert!(stats::stats().async_reporter_spans_dropped - dropped >= 4);
+        assert!(reporter.last_warning.is_some());
+
+        drop(tx);
+        reporter.shutdown();
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+const MAX_MEMBERS: usize = 180;
+
+/// The entries of a [W3C Baggage](https://www.w3.org/TR/baggage/) `baggage` header, carried by
+/// a [`TraceContext`](crate::collector::TraceContext) and inherited by all the spans of the trace.
+///
+/// Baggage is propagated to downstream services but is not reported with the spans. The entries
+/// are shared, so cloning a `Baggage` does not allocate.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::Baggage;
+///
+/// let baggage = Baggage::decode_w3c_baggage("userId=alice,serverNode=DF%2028;region=eu");
+/// assert_eq!(baggage.get("serverNode"), Some("DF 28"));
+///
+/// let baggage = baggage.insert("isProduction", "false");
+/// assert_eq!(
+///     baggage.encode_w3c_baggage(),
+///     "userId=alice,serverNode=DF%2028;region=eu,isProduction=false"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baggage(Option<Arc<Vec<Entry>>>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    key: String,
+    value: String,
+    /// The raw properties following the value, without the leading `;`.
+    metadata: Option<String>,
+}
+
+impl Baggage {
+    /// Creates a `Baggage` from key-value pairs.
+    pub fn new<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        entries
+            .into_iter()
+            .fold(Baggage::default(), |baggage, (k, v)| baggage.insert(k, v))
+    }
+
+    /// Decodes a `baggage` header string.
+    ///
+    /// Values are percent-decoded, and the properties of each entry are kept as is so that they
+    /// are propagated unchanged. Invalid members are skipped.
+    pub fn decode_w3c_baggage(baggage: &str) -> Self {
+        let mut entries: Vec<Entry> = Vec::new();
+        for member in baggage.split(',') {
+            let (pair, metadata) = match member.split_once(';') {
+                Some((pair, metadata)) => (pair, Some(metadata.trim())),
+                None => (member, None),
+            };
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            if key.is_empty() || !key.bytes().all(is_token_byte) {
+                continue;
+            }
+            let Some(value) = percent_decode(value.trim()) else {
+                continue;
+            };
+
+            let entry = Entry {
+                key: key.to_string(),
+                value,
+                metadata: metadata.filter(|m| !m.is_empty()).map(str::to_string),
+            };
+            match entries.iter().position(|e| e.key == entry.key) {
+                Some(i) => entries[i] = entry,
+                None if entries.len() < MAX_MEMBERS => entries.push(entry),
+                None => {}
+            }
+        }
+        Self::from_vec(entries)
+    }
+
+    /// Encodes the `Baggage` into a `baggage` header string.
+    pub fn encode_w3c_baggage(&self) -> String {
+        let mut baggage = String::new();
+        for (i, entry) in self.entries().iter().enumerate() {
+            if i > 0 {
+                baggage.push(',');
+            }
+            baggage.push_str(&entry.key);
+            baggage.push('=');
+            percent_encode(&entry.value, &mut baggage);
+            if let Some(metadata) = &entry.metadata {
+                baggage.push(';');
+                baggage.push_str(metadata);
+            }
+        }
+        baggage
+    }
+
+    /// Returns the value of the entry with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over the entries.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries()
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.value.as_str()))
+    }
+
+    /// Returns `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns a `Baggage` with the entry set to `value`.
+    pub fn insert(&self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let entry = Entry {
+            key: key.into(),
+            value: value.into(),
+            metadata: None,
+        };
+        let mut entries = self.entries().to_vec();
+        match entries.iter_mut().find(|e| e.key == entry.key) {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+        Self::from_vec(entries)
+    }
+
+    /// Returns a `Baggage` without the entry with the given key.
+    pub fn remove(&self, key: &str) -> Self {
+        Self::from_vec(
+            self.entries()
+                .iter()
+                .filter(|entry| entry.key != key)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn entries(&self) -> &[Entry] {
+        self.0.as_deref().map_or(&[], Vec::as_slice)
+    }
+
+    fn from_vec(entries: Vec<Entry>) -> Self {
+        if entries.is_empty() {
+            Baggage(None)
+        } else {
+            Baggage(Some(Arc::new(entries)))
+        }
+    }
+}
+
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn percent_encode(value: &str, out: &mut String) {
+    for b in value.bytes() {
+        let is_baggage_octet =
+            (0x21..=0x7e).contains(&b) && !matches!(b, b'"' | b',' | b';' | b'\\' | b'%');
+        if is_baggage_octet {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn w3c_baggage() {
+        let baggage = Baggage::decode_w3c_baggage(
+            "key1 = value1, key2=a%2Cb%25 ; prop1;prop2=v,invalid,=empty,key1=value2",
+        );
+        assert_eq!(baggage.iter().collect::<Vec<_>>(), vec![
+            ("key1", "value2"),
+            ("key2", "a,b%")
+        ]);
+        assert_eq!(
+            baggage.encode_w3c_baggage(),
+            "key1=value2,key2=a%2Cb%25;prop1;prop2=v"
+        );
+
+        assert_eq!(
+            Baggage::decode_w3c_baggage("bad=%zz,ok=%E2%9C%93").encode_w3c_baggage(),
+            "ok=%E2%9C%93"
+        );
+        assert_eq!(
+            Baggage::decode_w3c_baggage("ok=%E2%9C%93").get("ok"),
+            Some("✓")
+        );
+        assert!(Baggage::decode_w3c_baggage("").is_empty());
+    }
+
+    #[test]
+    fn update_baggage() {
+        let baggage = Baggage::new([("a", "1"), ("b", "2")]);
+        assert_eq!(baggage.insert("a", "3").encode_w3c_baggage(), "a=3,b=2");
+        assert_eq!(
+            baggage.insert("c", "x y").encode_w3c_baggage(),
+            "a=1,b=2,c=x%20y"
+        );
+        assert_eq!(baggage.remove("a").encode_w3c_baggage(), "b=2");
+        assert!(baggage.remove("a").remove("b").is_empty());
+        assert_eq!(baggage.get("b"), Some("2"));
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::collector::SpanRecord;
+use crate::collector::global_collector::Reporter;
+
+/// An identifier of a reporter registered in a [`FanoutReporter`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ReporterId(usize);
+
+/// A reporter that forwards every batch of span records to multiple reporters.
+///
+/// Each registered reporter receives its own copy of the batch. A panic raised by one reporter
+/// is caught and does not prevent the remaining reporters from receiving the batch.
+///
+/// Reporters can be added or removed at runtime through a [`FanoutHandle`], which remains usable
+/// after the `FanoutReporter` has been passed to [`set_reporter()`].
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::Config;
+/// use fastrace::collector::ConsoleReporter;
+/// use fastrace::collector::FanoutReporter;
+///
+/// let reporter = FanoutReporter::new().with_reporter(ConsoleReporter);
+/// let handle = reporter.handle();
+///
+/// fastrace::set_reporter(reporter, Config::default());
+///
+/// let id = handle.add_reporter(ConsoleReporter);
+/// handle.remove_reporter(id);
+/// ```
+///
+/// [`set_reporter()`]: crate::set_reporter
+#[derive(Default)]
+pub struct FanoutReporter {
+    shared: Arc<Mutex<Reporters>>,
+}
+
+/// A handle to add or remove reporters of a [`FanoutReporter`] at runtime.
+#[derive(Clone)]
+pub struct FanoutHandle {
+    shared: Arc<Mutex<Reporters>>,
+}
+
+#[derive(Default)]
+struct Reporters {
+    next_id: usize,
+    reporters: Vec<(ReporterId, Box<dyn Reporter>)>,
+}
+
+impl Reporters {
+    fn add(&mut self, reporter: Box<dyn Reporter>) -> ReporterId {
+        let id = ReporterId(self.next_id);
+        self.next_id += 1;
+        self.reporters.push((id, reporter));
+        id
+    }
+}
+
+impl FanoutReporter {
+    /// Creates a `FanoutReporter` without any downstream reporter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a downstream reporter and returns the modified `FanoutReporter`.
+    pub fn with_reporter(self, reporter: impl Reporter) -> Self {
+        self.shared.lock().add(Box::new(reporter));
+        self
+    }
+
+    /// Returns a handle that can add or remove downstream reporters at runtime.
+    pub fn handle(&self) -> FanoutHandle {
+        FanoutHandle {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl FanoutHandle {
+    /// Adds a downstream reporter. It will receive all batches reported from now on.
+    pub fn add_reporter(&self, reporter: impl Reporter) -> ReporterId {
+        self.shared.lock().add(Box::new(reporter))
+    }
+
+    /// Removes a downstream reporter and returns it, or `None` if it is not registered.
+    pub fn remove_reporter(&self, id: ReporterId) -> Option<Box<dyn Reporter>> {
+        let mut shared = self.shared.lock();
+        let index = shared.reporters.iter().position(|(i, _)| *i == id)?;
+        Some(shared.reporters.remove(index).1)
+    }
+}
+
+impl Reporter for FanoutReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        let mut shared = self.shared.lock();
+        let Some(((_, last), rest)) = shared.reporters.split_last_mut() else {
+            return;
+        };
+
+        for (_, reporter) in rest {
+            let spans = spans.clone();
+            std::panic::catch_unwind(AssertUnwindSafe(|| reporter.report(spans))).ok();
+        }
+        std::panic::catch_unwind(AssertUnwindSafe(|| last.report(spans))).ok();
+    }
+
+    fn shutdown(&mut self) {
+        for (_, reporter) in self.shared.lock().reporters.iter_mut() {
+            std::panic::catch_unwind(AssertUnwindSafe(|| reporter.shutdown())).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::TestReporter;
+
+    struct PanicReporter;
+
+    impl Reporter for PanicReporter {
+        fn report(&mut self, _spans: Vec<SpanRecord>) {
+            panic!("reporter panicked");
+        }
+    }
+
+    #[test]
+    fn fanout_isolates_panics() {
+        let (reporter1, spans1) = TestReporter::new();
+        let (reporter2, spans2) = TestReporter::new();
+        let mut fanout = FanoutReporter::new()
+            .with_reporter(reporter1)
+            .with_reporter(PanicReporter)
+            .with_reporter(reporter2);
+
+        fanout.report(vec![SpanRecord::default()]);
+
+        assert_eq!(spans1.lock().len(), 1);
+        assert_eq!(spans2.lock().len(), 1);
+    }
+
+    #[test]
+    fn fanout_add_remove() {
+        let (reporter1, spans1) = TestReporter::new();
+        let (reporter2, spans2) = TestReporter::new();
+        let mut fanout = FanoutReporter::new().with_reporter(reporter1);
+        let handle = fanout.handle();
+
+        let id = handle.add_reporter(reporter2);
+        fanout.report(vec![SpanRecord::default()]);
+        assert!(handle.remove_reporter(id).is_some());
+        assert!(handle.remove_reporter(id).is_none());
+        fanout.report(vec![SpanRecord::default()]);
+
+        assert_eq!(spans1.lock().len(), 2);
+        assert_eq!(spans2.lock().len(), 1);
+    }
+}
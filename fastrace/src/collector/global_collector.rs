@@ -3,31 +3,41 @@
 use std::borrow::Cow;
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU8;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use fastant::Anchor;
 use fastant::Instant;
 use parking_lot::Mutex;
 
 use crate::collector::Config;
+use crate::collector::DropPolicy;
 use crate::collector::EventRecord;
+use crate::collector::LinkRecord;
 use crate::collector::SpanContext;
 use crate::collector::SpanId;
 use crate::collector::SpanRecord;
 use crate::collector::SpanSet;
+use crate::collector::Status;
 use crate::collector::TraceId;
+use crate::collector::TraceState;
+use crate::collector::Value;
 use crate::collector::command::CancelCollect;
 use crate::collector::command::CollectCommand;
 use crate::collector::command::DropCollect;
 use crate::collector::command::StartCollect;
 use crate::collector::command::SubmitSpans;
+use crate::collector::stats;
 use crate::local::local_collector::LocalSpansInner;
 use crate::local::raw_span::RawKind;
 use crate::local::raw_span::RawSpan;
+use crate::local::thread_info::ThreadInfo;
 use crate::util::CollectToken;
 use crate::util::command_bus::CommandBus;
 use crate::util::command_bus::CommandSender;
@@ -36,14 +46,25 @@ static NEXT_COLLECT_ID: AtomicUsize = AtomicUsize::new(0);
 static GLOBAL_COLLECTOR: Mutex<Option<GlobalCollector>> = Mutex::new(None);
 static REPORTER_READY: AtomicBool = AtomicBool::new(false);
 static COMMAND_BUS: LazyLock<CommandBus<CollectCommand>> = LazyLock::new(CommandBus::new);
+#[cfg(not(target_family = "wasm"))]
+static COLLECTOR_THREAD: Mutex<Option<std::thread::JoinHandle<()>>> = Mutex::new(None);
+static MAX_PENDING_COMMANDS: AtomicUsize = AtomicUsize::new(usize::MAX);
+static DROP_POLICY: AtomicU8 = AtomicU8::new(DropPolicy::DropNewest as u8);
+/// The collects whose `StartCollect` was refused by a [`LimitedSender`]. It is shared by all the
+/// threads and the collector, rather than owned by the collector, so that the threads can drop
+/// the commands of a refused trace without waiting for the collector. A collect is removed when
+/// its `DropCollect` is sent, whichever thread sends it.
+static REFUSED_COLLECTS: LazyLock<Mutex<HashSet<usize>>> = LazyLock::new(Default::default);
+/// Whether [`REFUSED_COLLECTS`] is not empty, so that it is only locked while a trace is refused.
+static HAS_REFUSED_COLLECTS: AtomicBool = AtomicBool::new(false);
 
 pub const NOT_SAMPLED_COLLECT_ID: usize = usize::MAX;
 
 thread_local! {
-    static COMMAND_SENDER: UnsafeCell<CommandSender<CollectCommand>> = {
+    static COMMAND_SENDER: UnsafeCell<LimitedSender> = {
         const CHANNEL_SIZE: usize = 10240;
         let tx = COMMAND_BUS.sender(CHANNEL_SIZE);
-        UnsafeCell::new(tx)
+        UnsafeCell::new(LimitedSender::new(tx))
     };
 }
 
@@ -53,10 +74,221 @@ fn send_command(cmd: CollectCommand) {
     }
 
     COMMAND_SENDER
-        .try_with(|sender| unsafe { (*sender.get()).send(cmd) })
+        .try_with(|sender| {
+            let sender = unsafe { &mut *sender.get() };
+            let max_pending = MAX_PENDING_COMMANDS.load(Ordering::Relaxed);
+            sender.send(cmd, max_pending, drop_policy());
+        })
         .ok();
 }
 
+/// The command sender of a thread, which enforces [`Config::max_pending_commands()`].
+///
+/// Once the limit is reached, the traces started by the thread are refused, and the commands
+/// finishing a trace make room by dropping pending span submissions, so that the pending
+/// commands only exceed the limit if all of them finish traces already known to the collector.
+///
+/// All the commands of a refused trace are dropped, by any thread. The traces canceled by
+/// [`DropPolicy::DropTrace`] are canceled in the collector, which drops their spans.
+///
+/// [`Config::max_pending_commands()`]: crate::collector::Config::max_pending_commands
+struct LimitedSender {
+    sender: CommandSender<CollectCommand>,
+}
+
+impl LimitedSender {
+    fn new(sender: CommandSender<CollectCommand>) -> Self {
+        LimitedSender { sender }
+    }
+
+    fn send(&mut self, cmd: CollectCommand, max_pending: usize, drop_policy: DropPolicy) {
+        if max_pending == usize::MAX {
+            self.sender.send(cmd);
+            return;
+        }
+
+        self.sender.flush_pending();
+        let Some(cmd) = self.filter_dropped_collects(cmd) else {
+            return;
+        };
+        if self.sender.pending_len() < max_pending {
+            self.sender.send(cmd);
+            return;
+        }
+
+        match cmd {
+            CollectCommand::SubmitSpans(cmd) => match drop_policy {
+                DropPolicy::DropNewest => record_dropped_spans(&cmd),
+                DropPolicy::DropOldest => {
+                    if self.evict_oldest_submit() {
+                        self.sender.send(CollectCommand::SubmitSpans(cmd));
+                    } else {
+                        record_dropped_spans(&cmd);
+                    }
+                }
+                DropPolicy::DropTrace => {
+                    record_dropped_spans(&cmd);
+                    for item in &cmd.collect_token {
+                        self.cancel(item.collect_id);
+                    }
+                }
+            },
+            CollectCommand::StartCollect(StartCollect { collect_id }) => refuse_collect(collect_id),
+            CollectCommand::CancelCollect(CancelCollect { collect_id }) => self.cancel(collect_id),
+            CollectCommand::DropCollect(cmd) => {
+                if self.remove_pending_start(cmd.collect_id) {
+                    release_refused_collect(cmd.collect_id);
+                    return;
+                }
+                self.make_room();
+                self.sender.send(CollectCommand::DropCollect(cmd));
+            }
+        }
+    }
+
+    /// Drops the commands of the refused collects.
+    fn filter_dropped_collects(&mut self, cmd: CollectCommand) -> Option<CollectCommand> {
+        if !HAS_REFUSED_COLLECTS.load(Ordering::Relaxed) {
+            return Some(cmd);
+        }
+
+        match cmd {
+            CollectCommand::SubmitSpans(mut cmd) => {
+                let len = cmd.collect_token.len();
+                cmd.collect_token
+                    .retain(|item| !is_refused_collect(item.collect_id));
+                let dropped = len - cmd.collect_token.len();
+                if dropped > 0 {
+                    let spans = count_spans(&cmd.spans) * dropped;
+                    stats::SPANS_DROPPED.fetch_add(spans as u64, Ordering::Relaxed);
+                }
+                (!cmd.collect_token.is_empty()).then_some(CollectCommand::SubmitSpans(cmd))
+            }
+            CollectCommand::CancelCollect(CancelCollect { collect_id })
+                if is_refused_collect(collect_id) =>
+            {
+                None
+            }
+            CollectCommand::DropCollect(DropCollect { collect_id })
+                if release_refused_collect(collect_id) =>
+            {
+                None
+            }
+            cmd => Some(cmd),
+        }
+    }
+
+    /// Cancels a collect while the limit is reached, keeping at most one pending `CancelCollect`
+    /// for it.
+    fn cancel(&mut self, collect_id: usize) {
+        if self.remove_pending_start(collect_id) {
+            return;
+        }
+        let pending_cancel = self.sender.remove_pending(
+            |cmd| matches!(cmd, CollectCommand::CancelCollect(cmd) if cmd.collect_id == collect_id),
+        );
+        if pending_cancel.is_none() {
+            self.make_room();
+        }
+        self.sender
+            .send(CollectCommand::CancelCollect(CancelCollect { collect_id }));
+    }
+
+    /// Removes the pending `StartCollect` of a collect, if any, together with its pending spans,
+    /// and refuses the collect so that the collector never hears of it.
+    fn remove_pending_start(&mut self, collect_id: usize) -> bool {
+        let start = self.sender.remove_pending(
+            |cmd| matches!(cmd, CollectCommand::StartCollect(cmd) if cmd.collect_id == collect_id),
+        );
+        if start.is_none() {
+            return false;
+        }
+        while let Some(CollectCommand::SubmitSpans(cmd)) = self.sender.remove_pending(|cmd| {
+            matches!(cmd, CollectCommand::SubmitSpans(cmd)
+                if cmd.collect_token.iter().all(|item| item.collect_id == collect_id))
+        }) {
+            record_dropped_spans(&cmd);
+        }
+        refuse_collect(collect_id);
+        true
+    }
+
+    /// Drops the oldest pending span submission, or else refuses the oldest pending collect, to
+    /// make room for a command finishing a trace.
+    fn make_room(&mut self) {
+        if self.evict_oldest_submit() {
+            return;
+        }
+        if let Some(CollectCommand::StartCollect(StartCollect { collect_id })) = self
+            .sender
+            .remove_pending(|cmd| matches!(cmd, CollectCommand::StartCollect(_)))
+        {
+            // No span submission is pending, so the collect has no pending spans to remove.
+            refuse_collect(collect_id);
+        }
+    }
+
+    fn evict_oldest_submit(&mut self) -> bool {
+        match self
+            .sender
+            .remove_pending(|cmd| matches!(cmd, CollectCommand::SubmitSpans(_)))
+        {
+            Some(CollectCommand::SubmitSpans(oldest)) => {
+                record_dropped_spans(&oldest);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn refuse_collect(collect_id: usize) {
+    REFUSED_COLLECTS.lock().insert(collect_id);
+    HAS_REFUSED_COLLECTS.store(true, Ordering::Relaxed);
+}
+
+fn is_refused_collect(collect_id: usize) -> bool {
+    HAS_REFUSED_COLLECTS.load(Ordering::Relaxed) && REFUSED_COLLECTS.lock().contains(&collect_id)
+}
+
+/// Removes a refused collect, returning whether it was refused.
+fn release_refused_collect(collect_id: usize) -> bool {
+    if !HAS_REFUSED_COLLECTS.load(Ordering::Relaxed) {
+        return false;
+    }
+    let mut refused = REFUSED_COLLECTS.lock();
+    let released = refused.remove(&collect_id);
+    HAS_REFUSED_COLLECTS.store(!refused.is_empty(), Ordering::Relaxed);
+    released
+}
+
+fn record_dropped_spans(cmd: &SubmitSpans) {
+    let spans = count_spans(&cmd.spans) * cmd.collect_token.len();
+    stats::SPANS_DROPPED.fetch_add(spans as u64, Ordering::Relaxed);
+}
+
+fn count_spans(spans: &SpanSet) -> usize {
+    let count = |spans: &[RawSpan]| {
+        spans
+            .iter()
+            .filter(|span| span.raw_kind == RawKind::Span)
+            .count()
+    };
+    match spans {
+        SpanSet::Span(span, _) => count(std::slice::from_ref(span)),
+        SpanSet::LocalSpansInner(local_spans) => count(&local_spans.spans),
+        SpanSet::SharedLocalSpans(local_spans) => count(&local_spans.spans),
+    }
+}
+
+fn drop_policy() -> DropPolicy {
+    match DROP_POLICY.load(Ordering::Relaxed) {
+        x if x == DropPolicy::DropOldest as u8 => DropPolicy::DropOldest,
+        x if x == DropPolicy::DropTrace as u8 => DropPolicy::DropTrace,
+        _ => DropPolicy::DropNewest,
+    }
+}
+
 fn reporter_ready() -> bool {
     REPORTER_READY.load(Ordering::Relaxed)
 }
@@ -107,12 +339,63 @@ pub fn flush() {
     }
 }
 
+/// Reports all pending span records, shuts down the reporter and stops the background collector
+/// thread.
+///
+/// Traces whose root span is still alive are waited for up to `timeout`. After that, the spans
+/// collected so far for those traces are reported as they are. Then [`Reporter::shutdown()`] is
+/// called so that the reporter can flush its own buffers.
+///
+/// Spans created after the shutdown are ignored until [`set_reporter()`] is called again.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use fastrace::collector::Config;
+/// use fastrace::collector::ConsoleReporter;
+///
+/// fastrace::set_reporter(ConsoleReporter, Config::default());
+///
+/// // ...
+///
+/// fastrace::shutdown(Duration::from_secs(5));
+/// ```
+pub fn shutdown(timeout: Duration) {
+    #[cfg(feature = "enable")]
+    {
+        #[cfg(target_family = "wasm")]
+        {
+            let _ = timeout;
+            GlobalCollector::shutdown(Instant::now());
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            // Spawns a new thread to ensure the reporter operates outside the tokio runtime to
+            // prevent panic.
+            std::thread::Builder::new()
+                .name("fastrace-shutdown".to_string())
+                .spawn(move || GlobalCollector::shutdown(Instant::now() + timeout))
+                .unwrap()
+                .join()
+                .unwrap();
+        }
+    }
+}
+
 /// A trait defining the behavior of a reporter. A reporter is responsible for
 /// handling span records, typically by sending them to a remote service for
 /// further processing and analysis.
 pub trait Reporter: Send + 'static {
     /// Reports a batch of spans to a remote service.
     fn report(&mut self, spans: Vec<SpanRecord>);
+
+    /// Flushes any buffered spans and releases the resources held by the reporter.
+    ///
+    /// Called once by [`shutdown()`] after the last batch has been reported.
+    fn shutdown(&mut self) {}
 }
 
 #[derive(Default, Clone)]
@@ -178,11 +461,13 @@ enum SpanCollection {
         spans: SpanSet,
         trace_id: TraceId,
         parent_id: SpanId,
+        trace_state: TraceState,
     },
     Shared {
         spans: Arc<SpanSet>,
         trace_id: TraceId,
         parent_id: SpanId,
+        trace_state: TraceState,
     },
 }
 
@@ -219,6 +504,13 @@ pub(crate) struct GlobalCollector {
 
 impl GlobalCollector {
     fn start(reporter: impl Reporter, config: Config) {
+        MAX_PENDING_COMMANDS.store(
+            config.max_pending_commands.unwrap_or(usize::MAX),
+            Ordering::Relaxed,
+        );
+        DROP_POLICY.store(config.drop_policy as u8, Ordering::Relaxed);
+        REFUSED_COLLECTS.lock().clear();
+        HAS_REFUSED_COLLECTS.store(false, Ordering::Relaxed);
         REPORTER_READY.store(true, Ordering::Relaxed);
 
         let mut global_collector = GLOBAL_COLLECTOR.lock();
@@ -242,13 +534,16 @@ impl GlobalCollector {
 
             #[cfg(not(target_family = "wasm"))]
             {
-                std::thread::Builder::new()
+                let handle = std::thread::Builder::new()
                     .name("fastrace-global-collector".to_string())
                     .spawn(move || {
                         loop {
                             let report_interval = {
                                 let mut collector = GLOBAL_COLLECTOR.lock();
-                                let collector = collector.as_mut().unwrap();
+                                // The collector has been shut down.
+                                let Some(collector) = collector.as_mut() else {
+                                    break;
+                                };
                                 collector.handle_commands();
                                 collector.config.report_interval
                             };
@@ -257,10 +552,53 @@ impl GlobalCollector {
                         }
                     })
                     .unwrap();
+                *COLLECTOR_THREAD.lock() = Some(handle);
             }
         }
     }
 
+    fn shutdown(deadline: Instant) {
+        loop {
+            let mut global_collector = GLOBAL_COLLECTOR.lock();
+            let Some(collector) = global_collector.as_mut() else {
+                return;
+            };
+            collector.handle_commands();
+
+            if collector.active_collectors.is_empty() || Instant::now() >= deadline {
+                // Report the spans collected so far for the traces that are still alive.
+                for &collect_id in collector.active_collectors.keys() {
+                    send_command(CollectCommand::DropCollect(DropCollect { collect_id }));
+                }
+                REPORTER_READY.store(false, Ordering::Relaxed);
+
+                let mut collector = global_collector.take().unwrap();
+                drop(global_collector);
+
+                collector.handle_commands();
+                if let Some(reporter) = collector.reporter.as_mut() {
+                    reporter.shutdown();
+                }
+                break;
+            }
+
+            drop(global_collector);
+
+            #[cfg(not(target_family = "wasm"))]
+            std::thread::sleep(
+                deadline
+                    .saturating_duration_since(Instant::now())
+                    .min(Duration::from_millis(10)),
+            );
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(handle) = COLLECTOR_THREAD.lock().take() {
+            COMMAND_BUS.notify();
+            handle.join().ok();
+        }
+    }
+
     fn handle_commands(&mut self) {
         debug_assert!(self.start_collects.is_empty());
         debug_assert!(self.cancel_collects.is_empty());
@@ -268,13 +606,29 @@ impl GlobalCollector {
         debug_assert!(self.submit_spans.is_empty());
         debug_assert!(self.stale_spans.is_empty());
 
-        COMMAND_BUS.drain(|cmd| match cmd {
-            CollectCommand::StartCollect(cmd) => self.start_collects.push(cmd),
-            CollectCommand::CancelCollect(cmd) => self.cancel_collects.push(cmd),
-            CollectCommand::DropCollect(cmd) => self.drop_collects.push(cmd),
-            CollectCommand::SubmitSpans(cmd) => self.submit_spans.push(cmd),
+        let start = Instant::now();
+        let mut commands = 0;
+        let mut submitted_spans = 0;
+
+        COMMAND_BUS.drain(|cmd| {
+            commands += 1;
+            match cmd {
+                CollectCommand::StartCollect(cmd) => self.start_collects.push(cmd),
+                CollectCommand::CancelCollect(cmd) => self.cancel_collects.push(cmd),
+                CollectCommand::DropCollect(cmd) => self.drop_collects.push(cmd),
+                CollectCommand::SubmitSpans(cmd) => {
+                    submitted_spans += count_spans(&cmd.spans) * cmd.collect_token.len();
+                    self.submit_spans.push(cmd)
+                }
+            }
         });
 
+        stats::PENDING_COMMANDS_HIGH_WATER
+            .fetch_max(COMMAND_BUS.max_pending_len() as u64, Ordering::Relaxed);
+        stats::COMMANDS_RECEIVED.fetch_add(commands, Ordering::Relaxed);
+        stats::COMMANDS_PER_CYCLE_HIGH_WATER.fetch_max(commands, Ordering::Relaxed);
+        stats::SPANS_SUBMITTED.fetch_add(submitted_spans as u64, Ordering::Relaxed);
+
         // If the reporter is not set, global collector only clears the channel and then dismiss
         // all messages.
         if self.reporter.is_none() {
@@ -292,6 +646,9 @@ impl GlobalCollector {
 
         for CancelCollect { collect_id } in self.cancel_collects.drain(..) {
             if let Some(active_collector) = self.active_collectors.get_mut(&collect_id) {
+                if !active_collector.canceled {
+                    stats::CANCELED_COLLECTS.fetch_add(1, Ordering::Relaxed);
+                }
                 active_collector.span_collections.clear();
                 active_collector.danglings.clear();
                 active_collector.canceled = true;
@@ -300,13 +657,13 @@ impl GlobalCollector {
 
         for SubmitSpans {
             spans,
-            collect_token,
+            mut collect_token,
         } in self.submit_spans.drain(..)
         {
             debug_assert!(!collect_token.is_empty());
 
             if collect_token.len() == 1 {
-                let item = collect_token[0];
+                let item = collect_token.swap_remove(0);
                 if let Some(active_collector) = self.active_collectors.get_mut(&item.collect_id) {
                     if !active_collector.canceled {
                         active_collector
@@ -315,13 +672,18 @@ impl GlobalCollector {
                                 spans,
                                 trace_id: item.trace_id,
                                 parent_id: item.parent_id,
+                                trace_state: item.trace_state,
                             });
                     }
+                } else if is_refused_collect(item.collect_id) {
+                    stats::SPANS_DROPPED.fetch_add(count_spans(&spans) as u64, Ordering::Relaxed);
                 } else {
+                    stats::STALE_SPANS.fetch_add(count_spans(&spans) as u64, Ordering::Relaxed);
                     self.stale_spans.push(SpanCollection::Owned {
                         spans,
                         trace_id: item.trace_id,
                         parent_id: item.parent_id,
+                        trace_state: item.trace_state,
                     });
                 }
             } else {
@@ -336,13 +698,19 @@ impl GlobalCollector {
                                     spans: spans.clone(),
                                     trace_id: item.trace_id,
                                     parent_id: item.parent_id,
+                                    trace_state: item.trace_state.clone(),
                                 });
                         }
+                    } else if is_refused_collect(item.collect_id) {
+                        stats::SPANS_DROPPED
+                            .fetch_add(count_spans(&spans) as u64, Ordering::Relaxed);
                     } else {
+                        stats::STALE_SPANS.fetch_add(count_spans(&spans) as u64, Ordering::Relaxed);
                         self.stale_spans.push(SpanCollection::Shared {
                             spans: spans.clone(),
                             trace_id: item.trace_id,
                             parent_id: item.parent_id,
+                            trace_state: item.trace_state.clone(),
                         });
                     }
                 }
@@ -381,7 +749,11 @@ impl GlobalCollector {
 
         self.stale_spans.clear();
 
+        stats::RECORDS_REPORTED.fetch_add(committed_records.len() as u64, Ordering::Relaxed);
+        let report_start = Instant::now();
         self.reporter.as_mut().unwrap().report(committed_records);
+        stats::REPORT_DURATION.observe(report_start.elapsed());
+        stats::HANDLE_COMMANDS_DURATION.observe(start.elapsed());
     }
 }
 
@@ -394,6 +766,7 @@ impl LocalSpansInner {
             self,
             parent.trace_id,
             parent.span_id,
+            &TraceState::default(),
             &mut records,
             &mut danglings,
             &anchor,
@@ -405,7 +778,9 @@ impl LocalSpansInner {
 
 enum DanglingItem {
     Event(EventRecord),
-    Properties(Vec<(Cow<'static, str>, Cow<'static, str>)>),
+    Properties(Vec<(Cow<'static, str>, Value)>),
+    Links(Vec<LinkRecord>),
+    Status(Status),
 }
 
 fn postprocess_span_collection<'a>(
@@ -422,11 +797,14 @@ fn postprocess_span_collection<'a>(
                 spans,
                 trace_id,
                 parent_id,
+                trace_state,
             } => match spans {
-                SpanSet::Span(raw_span) => amend_span(
+                SpanSet::Span(raw_span, thread) => amend_span(
                     raw_span,
+                    thread,
                     *trace_id,
                     *parent_id,
+                    trace_state,
                     committed_records,
                     danglings,
                     anchor,
@@ -435,6 +813,7 @@ fn postprocess_span_collection<'a>(
                     local_spans,
                     *trace_id,
                     *parent_id,
+                    trace_state,
                     committed_records,
                     danglings,
                     anchor,
@@ -443,6 +822,7 @@ fn postprocess_span_collection<'a>(
                     local_spans,
                     *trace_id,
                     *parent_id,
+                    trace_state,
                     committed_records,
                     danglings,
                     anchor,
@@ -452,11 +832,14 @@ fn postprocess_span_collection<'a>(
                 spans,
                 trace_id,
                 parent_id,
+                trace_state,
             } => match &**spans {
-                SpanSet::Span(raw_span) => amend_span(
+                SpanSet::Span(raw_span, thread) => amend_span(
                     raw_span,
+                    thread,
                     *trace_id,
                     *parent_id,
+                    trace_state,
                     committed_records,
                     danglings,
                     anchor,
@@ -465,6 +848,7 @@ fn postprocess_span_collection<'a>(
                     local_spans,
                     *trace_id,
                     *parent_id,
+                    trace_state,
                     committed_records,
                     danglings,
                     anchor,
@@ -473,6 +857,7 @@ fn postprocess_span_collection<'a>(
                     local_spans,
                     *trace_id,
                     *parent_id,
+                    trace_state,
                     committed_records,
                     danglings,
                     anchor,
@@ -488,6 +873,7 @@ fn amend_local_span(
     local_spans: &LocalSpansInner,
     trace_id: TraceId,
     parent_id: SpanId,
+    trace_state: &TraceState,
     spans: &mut Vec<SpanRecord>,
     dangling: &mut HashMap<SpanId, Vec<DanglingItem>>,
     anchor: &Anchor,
@@ -515,6 +901,12 @@ fn amend_local_span(
                         .map(|p| p.to_vec())
                         .unwrap_or_default(),
                     events: vec![],
+                    links: span.links.as_deref().cloned().unwrap_or_default(),
+                    status: span.status.clone(),
+                    kind: span.kind,
+                    trace_state: trace_state.clone(),
+                    thread_id: Some(local_spans.thread.id),
+                    thread_name: local_spans.thread.name.clone(),
                 });
             }
             RawKind::Event => {
@@ -534,24 +926,31 @@ fn amend_local_span(
                     .push(DanglingItem::Event(event));
             }
             RawKind::Properties => {
-                dangling
-                    .entry(parent_id)
-                    .or_default()
-                    .push(DanglingItem::Properties(
-                        span.properties
-                            .as_ref()
-                            .map(|p| p.to_vec())
-                            .unwrap_or_default(),
-                    ));
+                let dangling = dangling.entry(parent_id).or_default();
+                dangling.push(DanglingItem::Properties(
+                    span.properties
+                        .as_ref()
+                        .map(|p| p.to_vec())
+                        .unwrap_or_default(),
+                ));
+                if let Some(links) = &span.links {
+                    dangling.push(DanglingItem::Links(links.to_vec()));
+                }
+                if span.status != Status::Unset {
+                    dangling.push(DanglingItem::Status(span.status.clone()));
+                }
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn amend_span(
     span: &RawSpan,
+    thread: &ThreadInfo,
     trace_id: TraceId,
     parent_id: SpanId,
+    trace_state: &TraceState,
     spans: &mut Vec<SpanRecord>,
     dangling: &mut HashMap<SpanId, Vec<DanglingItem>>,
     anchor: &Anchor,
@@ -573,6 +972,12 @@ fn amend_span(
                     .map(|p| p.to_vec())
                     .unwrap_or_default(),
                 events: vec![],
+                links: span.links.as_deref().cloned().unwrap_or_default(),
+                status: span.status.clone(),
+                kind: span.kind,
+                trace_state: trace_state.clone(),
+                thread_id: Some(thread.id),
+                thread_name: thread.name.clone(),
             });
         }
         RawKind::Event => {
@@ -592,15 +997,19 @@ fn amend_span(
                 .push(DanglingItem::Event(event));
         }
         RawKind::Properties => {
-            dangling
-                .entry(parent_id)
-                .or_default()
-                .push(DanglingItem::Properties(
-                    span.properties
-                        .as_ref()
-                        .map(|p| p.to_vec())
-                        .unwrap_or_default(),
-                ));
+            let dangling = dangling.entry(parent_id).or_default();
+            dangling.push(DanglingItem::Properties(
+                span.properties
+                    .as_ref()
+                    .map(|p| p.to_vec())
+                    .unwrap_or_default(),
+            ));
+            if let Some(links) = &span.links {
+                dangling.push(DanglingItem::Links(links.to_vec()));
+            }
+            if span.status != Status::Unset {
+                dangling.push(DanglingItem::Status(span.status.clone()));
+            }
         }
     }
 }
@@ -620,7 +1029,190 @@ fn mount_danglings(records: &mut [SpanRecord], danglings: &mut HashMap<SpanId, V
                     DanglingItem::Properties(properties) => {
                         record.properties.extend(properties);
                     }
+                    DanglingItem::Links(links) => {
+                        record.links.extend(links);
+                    }
+                    DanglingItem::Status(status) => {
+                        record.status = status;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+
+    use super::*;
+    use crate::collector::CollectTokenItem;
+    use crate::collector::TestReporter;
+
+    fn submit_spans(collect_id: usize) -> CollectCommand {
+        let span = RawSpan::begin_with(
+            SpanId::next_id(),
+            None,
+            Instant::ZERO,
+            "span",
+            RawKind::Span,
+        );
+        CollectCommand::SubmitSpans(SubmitSpans {
+            spans: SpanSet::Span(span, ThreadInfo::default()),
+            collect_token: CollectTokenItem {
+                trace_id: TraceId(1),
+                parent_id: SpanId::default(),
+                collect_id,
+                is_root: false,
+                is_sampled: true,
+                ..Default::default()
+            }
+            .into(),
+        })
+    }
+
+    fn drain(bus: &CommandBus<CollectCommand>) -> Vec<String> {
+        let mut cmds = vec![];
+        bus.drain(|cmd| {
+            cmds.push(match cmd {
+                CollectCommand::StartCollect(cmd) => format!("start {}", cmd.collect_id),
+                CollectCommand::CancelCollect(cmd) => format!("cancel {}", cmd.collect_id),
+                CollectCommand::DropCollect(cmd) => format!("drop {}", cmd.collect_id),
+                CollectCommand::SubmitSpans(cmd) => {
+                    format!("submit {}", cmd.collect_token[0].collect_id)
                 }
+            })
+        });
+        cmds
+    }
+
+    #[test]
+    #[serial]
+    fn pending_limit() {
+        for (policy, expected, dropped) in [
+            (
+                DropPolicy::DropNewest,
+                vec!["submit 1", "submit 2", "drop 1"],
+                2,
+            ),
+            (
+                DropPolicy::DropOldest,
+                vec!["submit 1", "drop 1", "submit 4"],
+                2,
+            ),
+            (
+                DropPolicy::DropTrace,
+                vec!["submit 1", "drop 1", "cancel 3", "cancel 4"],
+                3,
+            ),
+        ] {
+            let bus = CommandBus::new();
+            let mut sender = LimitedSender::new(bus.sender(1));
+            let before = stats::stats().spans_dropped;
+
+            for cmd in [
+                submit_spans(1),
+                submit_spans(2),
+                CollectCommand::DropCollect(DropCollect { collect_id: 1 }),
+                submit_spans(3),
+                submit_spans(4),
+            ] {
+                sender.send(cmd, 2, policy);
+            }
+
+            assert_eq!(drain_all(&bus, &mut sender), expected, "{policy:?}");
+            assert_eq!(stats::stats().spans_dropped - before, dropped, "{policy:?}");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn pending_limit_drop_trace() {
+        let bus = CommandBus::new();
+        let mut sender = LimitedSender::new(bus.sender(1));
+
+        for collect_id in 0..100 {
+            let cmds = std::iter::once(CollectCommand::StartCollect(StartCollect { collect_id }))
+                .chain((0..3).map(|_| submit_spans(collect_id)))
+                .chain([
+                    CollectCommand::CancelCollect(CancelCollect { collect_id }),
+                    CollectCommand::DropCollect(DropCollect { collect_id }),
+                ]);
+            for cmd in cmds {
+                sender.send(cmd, 2, DropPolicy::DropTrace);
+                assert!(sender.sender.pending_len() <= 2);
+            }
+        }
+
+        // The first collect fills the channel and is canceled, and the other ones are refused.
+        assert_eq!(drain_all(&bus, &mut sender), vec![
+            "start 0", "cancel 0", "drop 0"
+        ]);
+        assert!(REFUSED_COLLECTS.lock().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn refused_collect_on_another_thread() {
+        let (reporter, collected_spans) = TestReporter::new();
+        let mut collector = GlobalCollector {
+            config: Config::default(),
+            reporter: Some(Box::new(reporter)),
+            active_collectors: HashMap::new(),
+            start_collects: vec![],
+            cancel_collects: vec![],
+            drop_collects: vec![],
+            submit_spans: vec![],
+            stale_spans: vec![],
+        };
+        let before = stats::stats();
+
+        // The channel of the thread starting the root is full, so the collect is refused.
+        let mut sender = LimitedSender::new(COMMAND_BUS.sender(1));
+        for cmd in [
+            CollectCommand::StartCollect(StartCollect { collect_id: 0 }),
+            submit_spans(0),
+            CollectCommand::StartCollect(StartCollect { collect_id: 1 }),
+        ] {
+            sender.send(cmd, 1, DropPolicy::DropNewest);
+        }
+
+        // The root and its children are finished on another thread.
+        std::thread::spawn(|| {
+            let mut sender = LimitedSender::new(COMMAND_BUS.sender(16));
+            for cmd in [
+                submit_spans(1),
+                submit_spans(1),
+                CollectCommand::DropCollect(DropCollect { collect_id: 1 }),
+            ] {
+                sender.send(cmd, 1, DropPolicy::DropNewest);
+            }
+        })
+        .join()
+        .unwrap();
+
+        while sender.sender.pending_len() > 0 {
+            collector.handle_commands();
+            sender.sender.flush_pending();
+        }
+        collector.handle_commands();
+
+        let after = stats::stats();
+        assert!(collected_spans.lock().is_empty());
+        assert_eq!(after.spans_dropped - before.spans_dropped, 2);
+        assert_eq!(after.stale_spans, before.stale_spans);
+        assert_eq!(collector.active_collectors.keys().collect::<Vec<_>>(), [&0]);
+        assert!(REFUSED_COLLECTS.lock().is_empty());
+    }
+
+    fn drain_all(bus: &CommandBus<CollectCommand>, sender: &mut LimitedSender) -> Vec<String> {
+        let mut cmds = vec![];
+        loop {
+            cmds.extend(drain(bus));
+            sender.sender.flush_pending();
+            if sender.sender.pending_len() == 0 {
+                cmds.extend(drain(bus));
+                return cmds;
             }
         }
     }
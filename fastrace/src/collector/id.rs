@@ -29,6 +29,18 @@ impl TraceId {
     pub fn random() -> Self {
         TraceId(rand::random())
     }
+
+    /// Makes a deterministic sampling decision for `ratio` of the traces.
+    ///
+    /// The lower 64 bits are used because they are random for both W3C trace ids and
+    /// [`TraceId::random()`].
+    pub(crate) fn sampled_by_ratio(&self, ratio: f64) -> bool {
+        if ratio >= 1.0 {
+            return true;
+        }
+        let bound = (ratio.max(0.0) * u64::MAX as f64) as u64;
+        (self.0 as u64) < bound
+    }
 }
 
 impl fmt::Display for TraceId {
@@ -249,7 +261,7 @@ impl SpanContext {
             let stack = LOCAL_SPAN_STACK.try_with(Rc::clone).ok()?;
 
             let mut stack = stack.borrow_mut();
-            let collect_token = stack.current_collect_token()?[0];
+            let collect_token = &stack.current_collect_token()?[0];
 
             Some(Self {
                 trace_id: collect_token.trace_id,
@@ -4,17 +4,34 @@
 
 #![cfg_attr(test, allow(dead_code))]
 
+mod async_reporter;
+mod baggage;
 pub(crate) mod command;
 mod console_reporter;
+mod fanout_reporter;
 pub(crate) mod global_collector;
 pub(crate) mod id;
+mod rate_limiting_sampler;
+pub(crate) mod sampler;
+mod stats;
+mod tail_sampling;
 mod test_reporter;
+mod trace_context;
+mod trace_state;
+mod tree_console_reporter;
+mod value;
 
 use std::borrow::Cow;
 use std::sync::Arc;
 use std::time::Duration;
 
+pub use async_reporter::AsyncReporter;
+pub use async_reporter::AsyncReporterAdapter;
+pub use baggage::Baggage;
 pub use console_reporter::ConsoleReporter;
+pub use fanout_reporter::FanoutHandle;
+pub use fanout_reporter::FanoutReporter;
+pub use fanout_reporter::ReporterId;
 #[cfg(not(test))]
 pub(crate) use global_collector::GlobalCollect;
 #[cfg(test)]
@@ -23,11 +40,35 @@ pub use global_collector::Reporter;
 pub use id::SpanContext;
 pub use id::SpanId;
 pub use id::TraceId;
+pub use rate_limiting_sampler::RateLimitingSampler;
+pub use rate_limiting_sampler::SAMPLING_RATE_PROPERTY;
+pub use sampler::AlwaysSample;
+pub use sampler::NeverSample;
+pub use sampler::ParentBasedSampler;
+pub use sampler::PerNameSampler;
+pub use sampler::Sampler;
+pub use sampler::SamplingDecision;
+pub use sampler::TraceIdRatioSampler;
+pub use stats::HISTOGRAM_BOUNDS;
+pub use stats::Histogram;
+pub use stats::Stats;
+pub use stats::stats;
+pub use tail_sampling::ErrorPolicy;
+pub use tail_sampling::LatencyPolicy;
+pub use tail_sampling::PropertyPolicy;
+pub use tail_sampling::RatioPolicy;
+pub use tail_sampling::TailSamplingPolicy;
+pub use tail_sampling::TailSamplingReporter;
 #[doc(hidden)]
 pub use test_reporter::TestReporter;
+pub use trace_context::TraceContext;
+pub use trace_state::TraceState;
+pub use tree_console_reporter::TreeConsoleReporter;
+pub use value::Value;
 
 use crate::local::local_collector::LocalSpansInner;
 use crate::local::raw_span::RawSpan;
+use crate::local::thread_info::ThreadInfo;
 
 #[cfg(test)]
 pub(crate) type GlobalCollect = Arc<MockGlobalCollect>;
@@ -35,7 +76,7 @@ pub(crate) type GlobalCollect = Arc<MockGlobalCollect>;
 #[doc(hidden)]
 #[derive(Debug)]
 pub enum SpanSet {
-    Span(RawSpan),
+    Span(RawSpan, ThreadInfo),
     LocalSpansInner(LocalSpansInner),
     SharedLocalSpans(Arc<LocalSpansInner>),
 }
@@ -50,8 +91,59 @@ pub struct SpanRecord {
     pub begin_time_unix_ns: u64,
     pub duration_ns: u64,
     pub name: Cow<'static, str>,
-    pub properties: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    pub properties: Vec<(Cow<'static, str>, Value)>,
     pub events: Vec<EventRecord>,
+    pub links: Vec<LinkRecord>,
+    pub status: Status,
+    pub kind: SpanKind,
+    pub trace_state: TraceState,
+    /// The fastrace-assigned id of the thread the span ended on.
+    pub thread_id: Option<u64>,
+    /// The name of the thread the span ended on, if the thread is named.
+    pub thread_name: Option<Arc<str>>,
+}
+
+/// The status of a span, set by [`Span::set_status()`](crate::Span::set_status) or
+/// [`LocalSpan::set_status()`](crate::local::LocalSpan::set_status).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Status {
+    /// The status is not set.
+    #[default]
+    Unset,
+    /// The operation completed successfully.
+    Ok,
+    /// The operation failed, with a description of the error.
+    Error(Cow<'static, str>),
+}
+
+/// The role of a span in a trace, set by [`Span::with_kind()`](crate::Span::with_kind) or
+/// [`LocalSpan::with_kind()`](crate::local::LocalSpan::with_kind).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SpanKind {
+    /// The span handles a synchronous request from a remote client.
+    Server,
+    /// The span describes a synchronous request to a remote server.
+    Client,
+    /// The span describes a message sent to a broker, to be processed asynchronously.
+    Producer,
+    /// The span describes the processing of a message received from a broker.
+    Consumer,
+    /// The span describes an operation internal to the application.
+    #[default]
+    Internal,
+}
+
+impl SpanKind {
+    /// Returns the lowercase name of the kind, e.g. `"server"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpanKind::Server => "server",
+            SpanKind::Client => "client",
+            SpanKind::Producer => "producer",
+            SpanKind::Consumer => "consumer",
+            SpanKind::Internal => "internal",
+        }
+    }
 }
 
 /// A record of an event that occurred during the execution of a span.
@@ -59,17 +151,42 @@ pub struct SpanRecord {
 pub struct EventRecord {
     pub name: Cow<'static, str>,
     pub timestamp_unix_ns: u64,
-    pub properties: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    pub properties: Vec<(Cow<'static, str>, Value)>,
+}
+
+/// A record of a link from a span to another span, which may belong to a different trace.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkRecord {
+    pub trace_id: TraceId,
+    pub span_id: SpanId,
+    pub sampled: bool,
+    pub properties: Vec<(Cow<'static, str>, Value)>,
 }
 
 #[doc(hidden)]
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct CollectTokenItem {
     pub trace_id: TraceId,
     pub parent_id: SpanId,
     pub collect_id: usize,
     pub is_root: bool,
     pub is_sampled: bool,
+    pub trace_state: TraceState,
+    pub baggage: Baggage,
+}
+
+/// The policy applied when a thread has more pending commands than
+/// [`Config::max_pending_commands()`] allows.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum DropPolicy {
+    /// Drops the spans being submitted.
+    #[default]
+    DropNewest,
+    /// Drops the oldest pending spans to make room for the spans being submitted.
+    DropOldest,
+    /// Drops the spans being submitted and cancels the traces they belong to, so that no
+    /// partial trace is reported.
+    DropTrace,
 }
 
 /// Configuration of the behavior of the global collector.
@@ -77,6 +194,8 @@ pub struct CollectTokenItem {
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Config {
     pub(crate) report_interval: Duration,
+    pub(crate) max_pending_commands: Option<usize>,
+    pub(crate) drop_policy: DropPolicy,
 }
 
 impl Config {
@@ -96,7 +215,51 @@ impl Config {
     /// fastrace::set_reporter(fastrace::collector::ConsoleReporter, config);
     /// ```
     pub fn report_interval(self, report_interval: Duration) -> Self {
-        Self { report_interval }
+        Self {
+            report_interval,
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of commands that each thread may buffer while its channel to the
+    /// background collector thread is full, typically used to bound memory when the reporter
+    /// is stuck.
+    ///
+    /// Once the limit is reached, span submissions are dropped according to the
+    /// [`drop_policy()`](Config::drop_policy), and the traces started by the thread are dropped
+    /// entirely. The commands finishing a trace are not dropped, but make room by dropping
+    /// pending span submissions, so they only exceed the limit when all the pending commands
+    /// finish traces. The number of dropped spans is reported by [`stats()`].
+    ///
+    /// Defaults to `None`, which means unlimited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::Config;
+    /// use fastrace::collector::DropPolicy;
+    ///
+    /// let config = Config::default()
+    ///     .max_pending_commands(Some(1024))
+    ///     .drop_policy(DropPolicy::DropOldest);
+    /// fastrace::set_reporter(fastrace::collector::ConsoleReporter, config);
+    /// ```
+    pub fn max_pending_commands(self, max_pending_commands: Option<usize>) -> Self {
+        Self {
+            max_pending_commands,
+            ..self
+        }
+    }
+
+    /// Sets the policy applied when [`max_pending_commands()`](Config::max_pending_commands) is
+    /// reached.
+    ///
+    /// Defaults to [`DropPolicy::DropNewest`].
+    pub fn drop_policy(self, drop_policy: DropPolicy) -> Self {
+        Self {
+            drop_policy,
+            ..self
+        }
     }
 
     /// Configures whether to hold spans before the root span finishes.
@@ -123,6 +286,8 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             report_interval: Duration::from_secs(1),
+            max_pending_commands: None,
+            drop_policy: DropPolicy::default(),
         }
     }
 }
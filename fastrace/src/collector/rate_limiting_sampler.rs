@@ -0,0 +1,328 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use fastant::Instant;
+
+use crate::collector::Sampler;
+use crate::collector::SamplingDecision;
+use crate::collector::SpanContext;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const NO_RATE: u64 = u64::MAX;
+
+/// The property attached to sampled root spans by [`RateLimitingSampler`].
+pub const SAMPLING_RATE_PROPERTY: &str = "sampling.rate";
+
+/// A sampler that caps the number of sampled traces per second, both globally and per root
+/// span name.
+///
+/// Each limit is a token bucket that allows a burst of up to one second worth of traces. A
+/// trace is sampled only if both the bucket of its root span name, if any, and the global
+/// bucket have a token left; the token of the name is given back if the global bucket is
+/// empty. Names without a dedicated limit are only subject to the global limit. Decisions are
+/// made with atomic operations only.
+///
+/// Every sampled root span carries the property [`SAMPLING_RATE_PROPERTY`], which is the
+/// fraction of the root spans sampled during the previous second, so that backends can
+/// extrapolate the actual number of traces. The fraction is estimated separately for each name
+/// with a dedicated limit, and once for all the other names together.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::ParentBasedSampler;
+/// use fastrace::collector::RateLimitingSampler;
+///
+/// let sampler = RateLimitingSampler::new(100.0)
+///     .with_name_limit("GET /search", 10.0)
+///     .with_name_limit("POST /checkout", 50.0);
+///
+/// fastrace::set_sampler(ParentBasedSampler::new(sampler));
+/// ```
+pub struct RateLimitingSampler {
+    start: Instant,
+    global: TokenBucket,
+    names: HashMap<Cow<'static, str>, Limiter>,
+    others: Limiter,
+}
+
+struct Limiter {
+    bucket: Option<TokenBucket>,
+    rate: RateEstimator,
+}
+
+impl RateLimitingSampler {
+    /// Creates a sampler that samples at most `traces_per_second` traces per second in total.
+    ///
+    /// Use `f64::INFINITY` to apply only the per-name limits.
+    pub fn new(traces_per_second: f64) -> Self {
+        Self {
+            start: Instant::now(),
+            global: TokenBucket::new(traces_per_second),
+            names: HashMap::new(),
+            others: Limiter {
+                bucket: None,
+                rate: RateEstimator::new(),
+            },
+        }
+    }
+
+    /// Samples at most `traces_per_second` traces per second whose root span is named exactly
+    /// `name`.
+    pub fn with_name_limit(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        traces_per_second: f64,
+    ) -> Self {
+        self.names.insert(name.into(), Limiter {
+            bucket: Some(TokenBucket::new(traces_per_second)),
+            rate: RateEstimator::new(),
+        });
+        self
+    }
+
+    fn should_sample_at(&self, name: &str, now_ns: u64) -> SamplingDecision {
+        let limiter = self.names.get(name).unwrap_or(&self.others);
+        let sampled = match &limiter.bucket {
+            Some(bucket) => {
+                if !bucket.try_acquire(now_ns) {
+                    false
+                } else if self.global.try_acquire(now_ns) {
+                    true
+                } else {
+                    bucket.release();
+                    false
+                }
+            }
+            None => self.global.try_acquire(now_ns),
+        };
+
+        let rate = limiter.rate.record(now_ns, sampled);
+        if sampled {
+            SamplingDecision::sample().with_property(SAMPLING_RATE_PROPERTY, rate)
+        } else {
+            SamplingDecision::drop()
+        }
+    }
+}
+
+impl Sampler for RateLimitingSampler {
+    fn should_sample(&self, name: &str, _parent: &SpanContext) -> SamplingDecision {
+        let now_ns = Instant::now()
+            .saturating_duration_since(self.start)
+            .as_nanos() as u64;
+        self.should_sample_at(name, now_ns)
+    }
+}
+
+/// A token bucket implemented as a generic cell rate algorithm.
+struct TokenBucket {
+    interval_ns: u64,
+    burst_ns: u64,
+    /// The theoretical arrival time of the next trace, in nanoseconds since the sampler was
+    /// created.
+    tat: AtomicU64,
+}
+
+impl TokenBucket {
+    fn new(traces_per_second: f64) -> Self {
+        if traces_per_second.is_nan() || traces_per_second <= 0.0 {
+            return Self {
+                interval_ns: u64::MAX,
+                burst_ns: 0,
+                tat: AtomicU64::new(0),
+            };
+        }
+
+        let interval_ns = (NANOS_PER_SEC as f64 / traces_per_second) as u64;
+        Self {
+            interval_ns,
+            burst_ns: (interval_ns as f64 * traces_per_second.max(1.0)) as u64,
+            tat: AtomicU64::new(0),
+        }
+    }
+
+    fn try_acquire(&self, now_ns: u64) -> bool {
+        let mut tat = self.tat.load(Ordering::Relaxed);
+        loop {
+            let new_tat = tat.max(now_ns).saturating_add(self.interval_ns);
+            if new_tat - now_ns > self.burst_ns {
+                return false;
+            }
+            match self
+                .tat
+                .compare_exchange_weak(tat, new_tat, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return true,
+                Err(actual) => tat = actual,
+            }
+        }
+    }
+
+    /// Gives back a token taken by [`TokenBucket::try_acquire()`].
+    fn release(&self) {
+        self.tat.fetch_sub(self.interval_ns, Ordering::Relaxed);
+    }
+}
+
+/// Estimates the fraction of sampled traces over one-second windows.
+struct RateEstimator {
+    window: AtomicU64,
+    seen: AtomicU64,
+    sampled: AtomicU64,
+    /// The rate of the previous window as `f64` bits, or `NO_RATE` if unknown.
+    last_rate: AtomicU64,
+}
+
+impl RateEstimator {
+    fn new() -> Self {
+        Self {
+            window: AtomicU64::new(0),
+            seen: AtomicU64::new(0),
+            sampled: AtomicU64::new(0),
+            last_rate: AtomicU64::new(NO_RATE),
+        }
+    }
+
+    /// Records a decision and returns the estimated sampling rate.
+    fn record(&self, now_ns: u64, sampled: bool) -> f64 {
+        let window = now_ns / NANOS_PER_SEC;
+        let current = self.window.load(Ordering::Relaxed);
+        if window > current
+            && self
+                .window
+                .compare_exchange(current, window, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            let seen = self.seen.swap(0, Ordering::Relaxed);
+            let sampled = self.sampled.swap(0, Ordering::Relaxed);
+            let last_rate = if window == current + 1 && seen > 0 {
+                (sampled as f64 / seen as f64).to_bits()
+            } else {
+                NO_RATE
+            };
+            self.last_rate.store(last_rate, Ordering::Relaxed);
+        }
+
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed) + 1;
+        let sampled = if sampled {
+            self.sampled.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            self.sampled.load(Ordering::Relaxed)
+        };
+
+        match self.last_rate.load(Ordering::Relaxed) {
+            NO_RATE => (sampled as f64 / seen as f64).min(1.0),
+            bits => f64::from_bits(bits),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::Value;
+
+    fn sampled_count(
+        sampler: &RateLimitingSampler,
+        name: &str,
+        count: usize,
+        now_ns: u64,
+    ) -> usize {
+        (0..count)
+            .filter(|_| sampler.should_sample_at(name, now_ns).sampled)
+            .count()
+    }
+
+    #[test]
+    fn token_bucket() {
+        let bucket = TokenBucket::new(10.0);
+        assert_eq!((0..100).filter(|_| bucket.try_acquire(0)).count(), 10);
+        assert!(!bucket.try_acquire(NANOS_PER_SEC / 20));
+        assert!(bucket.try_acquire(NANOS_PER_SEC / 10));
+        assert_eq!(
+            (0..100)
+                .filter(|_| bucket.try_acquire(10 * NANOS_PER_SEC))
+                .count(),
+            10
+        );
+
+        let bucket = TokenBucket::new(0.5);
+        assert!(bucket.try_acquire(0));
+        assert!(!bucket.try_acquire(NANOS_PER_SEC));
+        assert!(bucket.try_acquire(2 * NANOS_PER_SEC));
+
+        let bucket = TokenBucket::new(0.0);
+        assert!(!bucket.try_acquire(0));
+        assert!(!bucket.try_acquire(100 * NANOS_PER_SEC));
+
+        let bucket = TokenBucket::new(f64::INFINITY);
+        assert_eq!((0..100).filter(|_| bucket.try_acquire(0)).count(), 100);
+    }
+
+    #[test]
+    fn rate_limiting_sampler() {
+        let sampler = RateLimitingSampler::new(20.0).with_name_limit("hot", 5.0);
+
+        assert_eq!(sampled_count(&sampler, "hot", 100, 0), 5);
+        assert_eq!(sampled_count(&sampler, "rare", 100, 0), 15);
+
+        let decision = sampler.should_sample_at("hot", NANOS_PER_SEC);
+        assert_eq!(decision.properties, vec![(
+            SAMPLING_RATE_PROPERTY.into(),
+            Value::F64(0.05)
+        )]);
+
+        let decision = sampler.should_sample_at("rare", NANOS_PER_SEC);
+        assert!(decision.sampled);
+        assert_eq!(decision.properties, vec![(
+            SAMPLING_RATE_PROPERTY.into(),
+            Value::F64(0.15)
+        )]);
+    }
+
+    #[test]
+    fn name_token_released() {
+        let sampler = RateLimitingSampler::new(10.0).with_name_limit("hot", 5.0);
+
+        assert_eq!(sampled_count(&sampler, "rare", 100, 0), 10);
+        assert_eq!(sampled_count(&sampler, "hot", 100, 0), 0);
+
+        // The global bucket has 5 tokens back, and "hot" still has all of its own.
+        assert_eq!(sampled_count(&sampler, "hot", 100, NANOS_PER_SEC / 2), 5);
+    }
+
+    #[test]
+    fn rate_without_previous_window() {
+        let sampler = RateLimitingSampler::new(1.0);
+
+        let decision = sampler.should_sample_at("root", 0);
+        assert_eq!(decision.properties, vec![(
+            SAMPLING_RATE_PROPERTY.into(),
+            Value::F64(1.0)
+        )]);
+
+        assert!(!sampler.should_sample_at("root", 0).sampled);
+        let decision = sampler.should_sample_at("root", 10 * NANOS_PER_SEC);
+        assert_eq!(decision.properties, vec![(
+            SAMPLING_RATE_PROPERTY.into(),
+            Value::F64(1.0)
+        )]);
+    }
+}
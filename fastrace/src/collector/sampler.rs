@@ -0,0 +1,286 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use parking_lot::RwLock;
+
+use crate::collector::SpanContext;
+use crate::collector::SpanId;
+use crate::collector::Value;
+
+static SAMPLER: RwLock<Option<Box<dyn Sampler>>> = RwLock::new(None);
+static SAMPLER_SET: AtomicBool = AtomicBool::new(false);
+
+/// A trait deciding whether a new trace started by [`Span::root()`] should be sampled.
+///
+/// [`Span::root()`]: crate::Span::root
+pub trait Sampler: Send + Sync + 'static {
+    /// Decides whether the trace should be sampled.
+    ///
+    /// `name` is the name of the root span, and `parent` is the [`SpanContext`] passed to
+    /// [`Span::root()`], which carries the trace id.
+    ///
+    /// [`Span::root()`]: crate::Span::root
+    fn should_sample(&self, name: &str, parent: &SpanContext) -> SamplingDecision;
+}
+
+impl<F> Sampler for F
+where F: Fn(&str, &SpanContext) -> bool + Send + Sync + 'static
+{
+    fn should_sample(&self, name: &str, parent: &SpanContext) -> SamplingDecision {
+        self(name, parent).into()
+    }
+}
+
+/// The decision made by a [`Sampler`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SamplingDecision {
+    /// Whether the trace is sampled.
+    pub sampled: bool,
+    /// Properties attached to the root span if the trace is sampled.
+    pub properties: Vec<(Cow<'static, str>, Value)>,
+}
+
+impl SamplingDecision {
+    /// Samples the trace.
+    pub fn sample() -> Self {
+        Self {
+            sampled: true,
+            properties: vec![],
+        }
+    }
+
+    /// Does not sample the trace.
+    pub fn drop() -> Self {
+        Self {
+            sampled: false,
+            properties: vec![],
+        }
+    }
+
+    /// Adds a property to be attached to the root span.
+    pub fn with_property(
+        mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<Value>,
+    ) -> Self {
+        self.properties.push((key.into(), value.into()));
+        self
+    }
+}
+
+impl From<bool> for SamplingDecision {
+    fn from(sampled: bool) -> Self {
+        Self {
+            sampled,
+            properties: vec![],
+        }
+    }
+}
+
+/// Sets the global sampler consulted by [`Span::root()`].
+///
+/// Without a sampler, a trace is sampled according to the `sampled` flag of the parent
+/// [`SpanContext`], which is the same as `ParentBasedSampler::new(AlwaysSample)`.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::ParentBasedSampler;
+/// use fastrace::collector::TraceIdRatioSampler;
+///
+/// fastrace::set_sampler(ParentBasedSampler::new(TraceIdRatioSampler::new(0.1)));
+/// ```
+///
+/// [`Span::root()`]: crate::Span::root
+pub fn set_sampler(sampler: impl Sampler) {
+    *SAMPLER.write() = Some(Box::new(sampler));
+    SAMPLER_SET.store(true, Ordering::Release);
+}
+
+#[inline]
+pub(crate) fn should_sample(name: &str, parent: &SpanContext) -> SamplingDecision {
+    if !SAMPLER_SET.load(Ordering::Acquire) {
+        return parent.sampled.into();
+    }
+
+    match SAMPLER.read().as_ref() {
+        Some(sampler) => sampler.should_sample(name, parent),
+        None => parent.sampled.into(),
+    }
+}
+
+/// A sampler that samples every trace.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysSample;
+
+impl Sampler for AlwaysSample {
+    fn should_sample(&self, _name: &str, _parent: &SpanContext) -> SamplingDecision {
+        SamplingDecision::sample()
+    }
+}
+
+/// A sampler that samples no trace.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NeverSample;
+
+impl Sampler for NeverSample {
+    fn should_sample(&self, _name: &str, _parent: &SpanContext) -> SamplingDecision {
+        SamplingDecision::drop()
+    }
+}
+
+/// A sampler that samples a fixed ratio of traces.
+///
+/// The decision is derived from the [`TraceId`], so that every service sampling the same trace
+/// with the same ratio makes the same decision.
+///
+/// [`TraceId`]: crate::collector::TraceId
+#[derive(Debug, Clone, Copy)]
+pub struct TraceIdRatioSampler {
+    ratio: f64,
+}
+
+impl TraceIdRatioSampler {
+    /// Creates a sampler that samples `ratio` of the traces. `ratio` is clamped to `[0.0, 1.0]`.
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Sampler for TraceIdRatioSampler {
+    fn should_sample(&self, _name: &str, parent: &SpanContext) -> SamplingDecision {
+        parent.trace_id.sampled_by_ratio(self.ratio).into()
+    }
+}
+
+/// A sampler that respects the decision of the parent, and delegates to another sampler for
+/// new traces.
+///
+/// A trace is not sampled if the parent [`SpanContext`] is marked as not sampled. If the parent
+/// refers to a remote span, i.e. its span id is not zero, the trace is sampled. Otherwise, the
+/// decision is delegated to the root sampler.
+#[derive(Debug, Clone, Copy)]
+pub struct ParentBasedSampler<S> {
+    root: S,
+}
+
+impl<S: Sampler> ParentBasedSampler<S> {
+    pub fn new(root: S) -> Self {
+        Self { root }
+    }
+}
+
+impl<S: Sampler> Sampler for ParentBasedSampler<S> {
+    fn should_sample(&self, name: &str, parent: &SpanContext) -> SamplingDecision {
+        if !parent.sampled {
+            SamplingDecision::drop()
+        } else if parent.span_id != SpanId::default() {
+            SamplingDecision::sample()
+        } else {
+            self.root.should_sample(name, parent)
+        }
+    }
+}
+
+/// A sampler that selects a sampler by the name of the root span.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::AlwaysSample;
+/// use fastrace::collector::NeverSample;
+/// use fastrace::collector::PerNameSampler;
+/// use fastrace::collector::TraceIdRatioSampler;
+///
+/// let sampler = PerNameSampler::new(TraceIdRatioSampler::new(0.1))
+///     .with_rule("GET /health", NeverSample)
+///     .with_rule("POST /checkout", AlwaysSample);
+///
+/// fastrace::set_sampler(sampler);
+/// ```
+pub struct PerNameSampler {
+    default: Box<dyn Sampler>,
+    rules: HashMap<Cow<'static, str>, Box<dyn Sampler>>,
+}
+
+impl PerNameSampler {
+    /// Creates a sampler that uses `default` for the root spans not matching any rule.
+    pub fn new(default: impl Sampler) -> Self {
+        Self {
+            default: Box::new(default),
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Uses `sampler` for the root spans named exactly `name`.
+    pub fn with_rule(mut self, name: impl Into<Cow<'static, str>>, sampler: impl Sampler) -> Self {
+        self.rules.insert(name.into(), Box::new(sampler));
+        self
+    }
+}
+
+impl Sampler for PerNameSampler {
+    fn should_sample(&self, name: &str, parent: &SpanContext) -> SamplingDecision {
+        self.rules
+            .get(name)
+            .unwrap_or(&self.default)
+            .should_sample(name, parent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::TraceId;
+
+    fn sampled(sampler: &impl Sampler, name: &str, parent: SpanContext) -> bool {
+        sampler.should_sample(name, &parent).sampled
+    }
+
+    #[test]
+    fn builtin_samplers() {
+        let local = SpanContext::new(TraceId(1), SpanId::default());
+        let remote = SpanContext::new(TraceId(1), SpanId(2));
+
+        assert!(sampled(&AlwaysSample, "root", local.sampled(false)));
+        assert!(!sampled(&NeverSample, "root", local));
+
+        assert!(sampled(&TraceIdRatioSampler::new(0.5), "root", local));
+        assert!(!sampled(
+            &TraceIdRatioSampler::new(0.5),
+            "root",
+            SpanContext::new(TraceId(u64::MAX as u128), SpanId::default())
+        ));
+
+        let parent_based = ParentBasedSampler::new(NeverSample);
+        assert!(sampled(&parent_based, "root", remote));
+        assert!(!sampled(&parent_based, "root", remote.sampled(false)));
+        assert!(!sampled(&parent_based, "root", local));
+
+        let per_name = PerNameSampler::new(NeverSample).with_rule("important", AlwaysSample);
+        assert!(sampled(&per_name, "important", local));
+        assert!(!sampled(&per_name, "other", local));
+
+        let closure = |name: &str, _: &SpanContext| name == "root";
+        assert!(sampled(&closure, "root", local));
+        assert!(!sampled(&closure, "other", local));
+    }
+}
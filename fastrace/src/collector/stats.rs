@@ -0,0 +1,175 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+pub(crate) static COMMANDS_RECEIVED: AtomicU64 = AtomicU64::new(0);
+pub(crate) static SPANS_SUBMITTED: AtomicU64 = AtomicU64::new(0);
+pub(crate) static SPANS_DROPPED: AtomicU64 = AtomicU64::new(0);
+pub(crate) static STALE_SPANS: AtomicU64 = AtomicU64::new(0);
+pub(crate) static CANCELED_COLLECTS: AtomicU64 = AtomicU64::new(0);
+pub(crate) static RECORDS_REPORTED: AtomicU64 = AtomicU64::new(0);
+pub(crate) static ASYNC_REPORTER_SPANS_DROPPED: AtomicU64 = AtomicU64::new(0);
+pub(crate) static PENDING_COMMANDS_HIGH_WATER: AtomicU64 = AtomicU64::new(0);
+pub(crate) static COMMANDS_PER_CYCLE_HIGH_WATER: AtomicU64 = AtomicU64::new(0);
+pub(crate) static HANDLE_COMMANDS_DURATION: AtomicHistogram = AtomicHistogram::new();
+pub(crate) static REPORT_DURATION: AtomicHistogram = AtomicHistogram::new();
+
+/// Upper bounds of the buckets of a [`Histogram`]. The last bucket of a histogram counts
+/// everything above the last bound.
+pub const HISTOGRAM_BOUNDS: [Duration; 7] = [
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+    Duration::from_secs(10),
+];
+
+const BUCKETS: usize = HISTOGRAM_BOUNDS.len() + 1;
+
+/// A snapshot of the counters of the collector pipeline, returned by [`stats()`].
+///
+/// All counters are cumulative since the start of the process.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Number of commands received by the background collector.
+    pub commands_received: u64,
+    /// Number of spans submitted to the background collector.
+    pub spans_submitted: u64,
+    /// Number of spans dropped because a thread exceeded
+    /// [`Config::max_pending_commands()`](crate::collector::Config::max_pending_commands).
+    pub spans_dropped: u64,
+    /// Number of spans submitted after their root span had finished.
+    pub stale_spans: u64,
+    /// Number of traces canceled by [`Span::cancel()`](crate::Span::cancel), or because a
+    /// thread exceeded its pending limit under
+    /// [`DropPolicy::DropTrace`](crate::collector::DropPolicy::DropTrace).
+    pub canceled_collects: u64,
+    /// Number of span records passed to the reporter.
+    pub records_reported: u64,
+    /// Number of span records dropped by an
+    /// [`AsyncReporterAdapter`](crate::collector::AsyncReporterAdapter) because its queue was
+    /// full.
+    pub async_reporter_spans_dropped: u64,
+    /// The largest number of commands a single thread has buffered while its channel was full,
+    /// as sampled by the collector at each collection cycle.
+    pub pending_commands_high_water: u64,
+    /// The largest number of commands drained in a single collection cycle.
+    pub commands_per_cycle_high_water: u64,
+    /// Time spent in each collection cycle, including reporting.
+    pub handle_commands_duration: Histogram,
+    /// Time spent in [`Reporter::report()`](crate::collector::Reporter::report).
+    pub report_duration: Histogram,
+}
+
+/// A histogram of durations with buckets bounded by [`HISTOGRAM_BOUNDS`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Histogram {
+    /// Number of observations per bucket. `counts[i]` counts the observations not greater than
+    /// `HISTOGRAM_BOUNDS[i]` and greater than the previous bound; the last element counts the
+    /// observations greater than every bound.
+    pub counts: Vec<u64>,
+    /// Total number of observations.
+    pub count: u64,
+    /// Sum of all observations.
+    pub sum: Duration,
+}
+
+/// Returns a snapshot of the counters of the collector pipeline.
+///
+/// # Examples
+///
+/// ```
+/// let stats = fastrace::collector::stats();
+/// println!("reported {} span records", stats.records_reported);
+/// ```
+pub fn stats() -> Stats {
+    Stats {
+        commands_received: COMMANDS_RECEIVED.load(Ordering::Relaxed),
+        spans_submitted: SPANS_SUBMITTED.load(Ordering::Relaxed),
+        spans_dropped: SPANS_DROPPED.load(Ordering::Relaxed),
+        stale_spans: STALE_SPANS.load(Ordering::Relaxed),
+        canceled_collects: CANCELED_COLLECTS.load(Ordering::Relaxed),
+        records_reported: RECORDS_REPORTED.load(Ordering::Relaxed),
+        async_reporter_spans_dropped: ASYNC_REPORTER_SPANS_DROPPED.load(Ordering::Relaxed),
+        pending_commands_high_water: PENDING_COMMANDS_HIGH_WATER.load(Ordering::Relaxed),
+        commands_per_cycle_high_water: COMMANDS_PER_CYCLE_HIGH_WATER.load(Ordering::Relaxed),
+        handle_commands_duration: HANDLE_COMMANDS_DURATION.snapshot(),
+        report_duration: REPORT_DURATION.snapshot(),
+    }
+}
+
+pub(crate) struct AtomicHistogram {
+    counts: [AtomicU64; BUCKETS],
+    count: AtomicU64,
+    sum_ns: AtomicU64,
+}
+
+impl AtomicHistogram {
+    const fn new() -> Self {
+        Self {
+            counts: [const { AtomicU64::new(0) }; BUCKETS],
+            count: AtomicU64::new(0),
+            sum_ns: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn observe(&self, duration: Duration) {
+        let bucket = HISTOGRAM_BOUNDS
+            .iter()
+            .position(|bound| duration <= *bound)
+            .unwrap_or(BUCKETS - 1);
+        self.counts[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_ns
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> Histogram {
+        Histogram {
+            counts: self
+                .counts
+                .iter()
+                .map(|count| count.load(Ordering::Relaxed))
+                .collect(),
+            count: self.count.load(Ordering::Relaxed),
+            sum: Duration::from_nanos(self.sum_ns.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram() {
+        let histogram = AtomicHistogram::new();
+        histogram.observe(Duration::from_micros(1));
+        histogram.observe(Duration::from_micros(10));
+        histogram.observe(Duration::from_millis(5));
+        histogram.observe(Duration::from_secs(60));
+
+        assert_eq!(histogram.snapshot(), Histogram {
+            counts: vec![2, 0, 0, 1, 0, 0, 0, 1],
+            count: 4,
+            sum: Duration::from_micros(60_005_011),
+        });
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::collector::SpanRecord;
+use crate::collector::Status;
+use crate::collector::TraceId;
+use crate::collector::Value;
+use crate::collector::global_collector::Reporter;
+
+const DEFAULT_DECISION_CACHE_SIZE: usize = 10240;
+
+/// A policy deciding whether a finished trace should be kept by a [`TailSamplingReporter`].
+pub trait TailSamplingPolicy: Send + 'static {
+    /// Returns `true` if the trace should be reported.
+    ///
+    /// `spans` contains all span records of the trace, and `root` is the span record that has
+    /// no parent within the trace.
+    fn keep(&self, root: &SpanRecord, spans: &[SpanRecord]) -> bool;
+}
+
+impl<F> TailSamplingPolicy for F
+where F: Fn(&SpanRecord, &[SpanRecord]) -> bool + Send + 'static
+{
+    fn keep(&self, root: &SpanRecord, spans: &[SpanRecord]) -> bool {
+        self(root, spans)
+    }
+}
+
+/// Keeps the traces containing a span with [`Status::Error`], or with the property `error` set to
+/// `true` or `"true"`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorPolicy;
+
+impl TailSamplingPolicy for ErrorPolicy {
+    fn keep(&self, _root: &SpanRecord, spans: &[SpanRecord]) -> bool {
+        spans.iter().any(|span| {
+            matches!(span.status, Status::Error(_))
+                || has_property(span, "error", &Value::Bool(true))
+                || has_property(span, "error", &Value::Str("true".into()))
+        })
+    }
+}
+
+/// Keeps the traces whose root span lasts longer than a threshold.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyPolicy {
+    threshold: Duration,
+}
+
+impl LatencyPolicy {
+    /// Creates a policy that keeps the traces whose root span lasts longer than `threshold`.
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold }
+    }
+}
+
+impl TailSamplingPolicy for LatencyPolicy {
+    fn keep(&self, root: &SpanRecord, _spans: &[SpanRecord]) -> bool {
+        Duration::from_nanos(root.duration_ns) > self.threshold
+    }
+}
+
+/// Keeps a fixed ratio of traces.
+///
+/// The decision is derived from the [`TraceId`], so that every service sampling the same trace
+/// with the same ratio makes the same decision.
+#[derive(Debug, Clone, Copy)]
+pub struct RatioPolicy {
+    ratio: f64,
+}
+
+impl RatioPolicy {
+    /// Creates a policy that keeps `ratio` of the traces. `ratio` is clamped to `[0.0, 1.0]`.
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl TailSamplingPolicy for RatioPolicy {
+    fn keep(&self, root: &SpanRecord, _spans: &[SpanRecord]) -> bool {
+        root.trace_id.sampled_by_ratio(self.ratio)
+    }
+}
+
+/// Keeps the traces containing a span with the given property.
+#[derive(Debug, Clone)]
+pub struct PropertyPolicy {
+    key: Cow<'static, str>,
+    value: Value,
+}
+
+impl PropertyPolicy {
+    /// Creates a policy that keeps the traces containing a span with the property `key` equal
+    /// to `value`.
+    pub fn new(key: impl Into<Cow<'static, str>>, value: impl Into<Value>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl TailSamplingPolicy for PropertyPolicy {
+    fn keep(&self, _root: &SpanRecord, spans: &[SpanRecord]) -> bool {
+        spans
+            .iter()
+            .any(|span| has_property(span, &self.key, &self.value))
+    }
+}
+
+fn has_property(span: &SpanRecord, key: &str, value: &Value) -> bool {
+    span.properties.iter().any(|(k, v)| k == key && v == value)
+}
+
+/// A reporter that decides whether to keep a trace after it has finished, and forwards only
+/// the kept traces to the downstream reporter.
+///
+/// The global collector holds the spans of a trace until its root span finishes, so a trace
+/// arrives at the reporter as a whole. A trace is kept if any of the policies keeps it. If no
+/// policy is configured, every trace is kept.
+///
+/// Decisions are remembered for the most recent traces, so that spans submitted after the root
+/// span has finished follow the decision made for their trace.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use fastrace::collector::Config;
+/// use fastrace::collector::ConsoleReporter;
+/// use fastrace::collector::ErrorPolicy;
+/// use fastrace::collector::LatencyPolicy;
+/// use fastrace::collector::RatioPolicy;
+/// use fastrace::collector::TailSamplingReporter;
+///
+/// let reporter = TailSamplingReporter::new(ConsoleReporter)
+///     .with_policy(ErrorPolicy)
+///     .with_policy(LatencyPolicy::new(Duration::from_millis(100)))
+///     .with_policy(RatioPolicy::new(0.01));
+///
+/// fastrace::set_reporter(reporter, Config::default());
+/// ```
+pub struct TailSamplingReporter<R> {
+    reporter: R,
+    policies: Vec<Box<dyn TailSamplingPolicy>>,
+    decisions: HashMap<TraceId, bool>,
+    decision_order: VecDeque<TraceId>,
+    decision_cache_size: usize,
+}
+
+impl<R: Reporter> TailSamplingReporter<R> {
+    /// Creates a reporter that forwards the kept traces to `reporter`. Keeps every trace until
+    /// a policy is added.
+    pub fn new(reporter: R) -> Self {
+        Self {
+            reporter,
+            policies: vec![],
+            decisions: HashMap::new(),
+            decision_order: VecDeque::new(),
+            decision_cache_size: DEFAULT_DECISION_CACHE_SIZE,
+        }
+    }
+
+    /// Adds a policy. A trace is kept if any of the policies keeps it.
+    pub fn with_policy(mut self, policy: impl TailSamplingPolicy) -> Self {
+        self.policies.push(Box::new(policy));
+        self
+    }
+
+    /// Sets the number of recent decisions to remember.
+    ///
+    /// Defaults to 10240.
+    pub fn with_decision_cache_size(mut self, decision_cache_size: usize) -> Self {
+        self.decision_cache_size = decision_cache_size;
+        self
+    }
+
+    fn decide(&self, spans: &[SpanRecord]) -> bool {
+        if self.policies.is_empty() {
+            return true;
+        }
+
+        let span_ids = spans
+            .iter()
+            .map(|span| span.span_id)
+            .collect::<HashSet<_>>();
+        let Some(root) = spans
+            .iter()
+            .filter(|span| !span_ids.contains(&span.parent_id))
+            .min_by_key(|span| span.begin_time_unix_ns)
+        else {
+            return true;
+        };
+
+        self.policies.iter().any(|policy| policy.keep(root, spans))
+    }
+
+    fn remember(&mut self, trace_id: TraceId, keep: bool) {
+        if self.decision_cache_size == 0 {
+            return;
+        }
+
+        while self.decision_order.len() >= self.decision_cache_size {
+            if let Some(oldest) = self.decision_order.pop_front() {
+                self.decisions.remove(&oldest);
+            }
+        }
+        self.decisions.insert(trace_id, keep);
+        self.decision_order.push_back(trace_id);
+    }
+}
+
+impl<R: Reporter> Reporter for TailSamplingReporter<R> {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        let mut traces: Vec<(TraceId, Vec<SpanRecord>)> = vec![];
+        let mut trace_index = HashMap::new();
+        for span in spans {
+            let index = *trace_index.entry(span.trace_id).or_insert_with(|| {
+                traces.push((span.trace_id, vec![]));
+                traces.len() - 1
+            });
+            traces[index].1.push(span);
+        }
+
+        let mut kept = vec![];
+        for (trace_id, spans) in traces {
+            let keep = match self.decisions.get(&trace_id) {
+                Some(keep) => *keep,
+                None => {
+                    let keep = self.decide(&spans);
+                    self.remember(trace_id, keep);
+                    keep
+                }
+            };
+            if keep {
+                kept.extend(spans);
+            }
+        }
+
+        self.reporter.report(kept);
+    }
+
+    fn shutdown(&mut self) {
+        self.reporter.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::SpanId;
+    use crate::collector::TestReporter;
+
+    fn span(trace_id: u128, span_id: u64, parent_id: u64, duration_ns: u64) -> SpanRecord {
+        SpanRecord {
+            trace_id: TraceId(trace_id),
+            span_id: SpanId(span_id),
+            parent_id: SpanId(parent_id),
+            duration_ns,
+            ..SpanRecord::default()
+        }
+    }
+
+    #[test]
+    fn tail_sampling() {
+        let (reporter, spans) = TestReporter::new();
+        let mut reporter = TailSamplingReporter::new(reporter)
+            .with_policy(ErrorPolicy)
+            .with_policy(LatencyPolicy::new(Duration::from_millis(100)))
+            .with_policy(PropertyPolicy::new("user", "alice"));
+
+        let mut error = span(1, 2, 1, 10);
+        error.properties.push(("error".into(), true.into()));
+        let mut failed = span(5, 2, 1, 10);
+        failed.status = Status::Error("failed".into());
+        let mut alice = span(4, 2, 0, 10);
+        alice.properties.push(("user".into(), "alice".into()));
+        reporter.report(vec![
+            span(1, 1, 0, 10),
+            error,
+            span(2, 1, 0, 10),
+            span(2, 2, 1, 1_000_000_000),
+            span(3, 1, 0, 1_000_000_000),
+            span(3, 2, 1, 10),
+            span(4, 1, 0, 10),
+            alice,
+            span(5, 1, 0, 10),
+            failed,
+        ]);
+        // Stale spans follow the decision made for their trace.
+        reporter.report(vec![span(1, 3, 1, 10), span(2, 3, 1, 10)]);
+
+        let trace_ids = spans
+            .lock()
+            .iter()
+            .map(|span| (span.trace_id.0, span.span_id.0))
+            .collect::<Vec<_>>();
+        assert_eq!(trace_ids, vec![
+            (1, 1),
+            (1, 2),
+            (3, 1),
+            (3, 2),
+            (4, 1),
+            (4, 2),
+            (5, 1),
+            (5, 2),
+            (1, 3)
+        ]);
+    }
+
+    #[test]
+    fn ratio_policy() {
+        let policy = RatioPolicy::new(0.5);
+        let kept = (0..10000)
+            .filter(|_| policy.keep(&span(TraceId::random().0, 1, 0, 0), &[]))
+            .count();
+        assert!((4000..6000).contains(&kept));
+
+        assert!(!RatioPolicy::new(0.0).keep(&span(u128::MAX, 1, 0, 0), &[]));
+        assert!(RatioPolicy::new(1.0).keep(&span(u128::MAX, 1, 0, 0), &[]));
+    }
+}
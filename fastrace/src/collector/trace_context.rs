@@ -0,0 +1,174 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use crate::Span;
+use crate::collector::Baggage;
+use crate::collector::SpanContext;
+use crate::collector::TraceState;
+use crate::local::local_span_stack::LOCAL_SPAN_STACK;
+
+/// A [`SpanContext`] along with the [`TraceState`] and [`Baggage`] propagated with it.
+///
+/// The trace state and baggage of the context passed to [`Span::root_with_context()`] are
+/// inherited by all the spans of the trace, and can be read back from
+/// [`TraceContext::from_span()`] or [`TraceContext::current_local_parent()`].
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::Baggage;
+/// use fastrace::collector::TraceContext;
+/// use fastrace::prelude::*;
+///
+/// let parent =
+///     TraceContext::new(SpanContext::random()).with_baggage(Baggage::new([("user.id", "alice")]));
+/// let root = Span::root_with_context("root", parent);
+///
+/// let context = TraceContext::from_span(&root).unwrap();
+/// assert_eq!(context.baggage.get("user.id"), Some("alice"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TraceContext {
+    pub span_context: SpanContext,
+    pub trace_state: TraceState,
+    pub baggage: Baggage,
+}
+
+impl TraceContext {
+    /// Creates a new `TraceContext` with the given [`SpanContext`], and an empty trace state and
+    /// baggage.
+    pub fn new(span_context: SpanContext) -> Self {
+        Self {
+            span_context,
+            trace_state: TraceState::default(),
+            baggage: Baggage::default(),
+        }
+    }
+
+    /// Sets the [`TraceState`] of the `TraceContext`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::TraceContext;
+    /// use fastrace::collector::TraceState;
+    /// use fastrace::prelude::*;
+    ///
+    /// let context = TraceContext::new(SpanContext::new(TraceId(12), SpanId(34)))
+    ///     .with_trace_state(TraceState::decode_w3c_tracestate("congo=t61rcWkgMzE"));
+    /// ```
+    pub fn with_trace_state(mut self, trace_state: TraceState) -> Self {
+        self.trace_state = trace_state;
+        self
+    }
+
+    /// Sets the [`Baggage`] of the `TraceContext`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::Baggage;
+    /// use fastrace::collector::TraceContext;
+    /// use fastrace::prelude::*;
+    ///
+    /// let context = TraceContext::new(SpanContext::new(TraceId(12), SpanId(34)))
+    ///     .with_baggage(Baggage::new([("user.id", "alice")]));
+    /// ```
+    pub fn with_baggage(mut self, baggage: Baggage) -> Self {
+        self.baggage = baggage;
+        self
+    }
+
+    /// Creates a `TraceContext` from the given [`Span`]. If the `Span` is a noop span,
+    /// this function will return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::TraceContext;
+    /// use fastrace::prelude::*;
+    ///
+    /// let span = Span::root("root", SpanContext::random());
+    /// let context = TraceContext::from_span(&span);
+    /// ```
+    pub fn from_span(span: &Span) -> Option<Self> {
+        #[cfg(not(feature = "enable"))]
+        {
+            None
+        }
+
+        #[cfg(feature = "enable")]
+        {
+            let inner = span.inner.as_ref()?;
+            let collect_token = inner.issue_collect_token().next()?;
+
+            Some(Self {
+                span_context: SpanContext {
+                    trace_id: collect_token.trace_id,
+                    span_id: collect_token.parent_id,
+                    sampled: collect_token.is_sampled,
+                },
+                trace_state: collect_token.trace_state,
+                baggage: collect_token.baggage,
+            })
+        }
+    }
+
+    /// Creates a `TraceContext` from the current local parent span. If there is no
+    /// local parent span, this function will return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::TraceContext;
+    /// use fastrace::prelude::*;
+    ///
+    /// let span = Span::root("root", SpanContext::random());
+    /// let _guard = span.set_local_parent();
+    ///
+    /// let context = TraceContext::current_local_parent();
+    /// ```
+    pub fn current_local_parent() -> Option<Self> {
+        #[cfg(not(feature = "enable"))]
+        {
+            None
+        }
+
+        #[cfg(feature = "enable")]
+        {
+            let stack = LOCAL_SPAN_STACK.try_with(Rc::clone).ok()?;
+
+            let mut stack = stack.borrow_mut();
+            let collect_token = stack.current_collect_token()?.swap_remove(0);
+
+            Some(Self {
+                span_context: SpanContext {
+                    trace_id: collect_token.trace_id,
+                    span_id: collect_token.parent_id,
+                    sampled: collect_token.is_sampled,
+                },
+                trace_state: collect_token.trace_state,
+                baggage: collect_token.baggage,
+            })
+        }
+    }
+}
+
+impl From<SpanContext> for TraceContext {
+    fn from(span_context: SpanContext) -> Self {
+        Self::new(span_context)
+    }
+}
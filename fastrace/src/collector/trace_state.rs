@@ -0,0 +1,222 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+const MAX_MEMBERS: usize = 32;
+
+/// The vendor-specific entries of a [W3C Trace Context](https://www.w3.org/TR/trace-context/#tracestate-header)
+/// `tracestate` header, carried by a [`TraceContext`](crate::collector::TraceContext) and inherited
+/// by all the spans of the trace.
+///
+/// The entries are shared, so cloning a `TraceState` does not allocate.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::TraceState;
+///
+/// let trace_state = TraceState::decode_w3c_tracestate("congo=t61rcWkgMzE,rojo=00f067aa0ba902b7");
+/// assert_eq!(trace_state.get("rojo"), Some("00f067aa0ba902b7"));
+///
+/// let trace_state = trace_state.insert("congo", "ucfJifl5GOE");
+/// assert_eq!(
+///     trace_state.encode_w3c_tracestate(),
+///     "congo=ucfJifl5GOE,rojo=00f067aa0ba902b7"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceState(Option<Arc<Vec<(String, String)>>>);
+
+impl TraceState {
+    /// Creates a `TraceState` from key-value pairs, in the order they should be propagated.
+    ///
+    /// Keys and values must be valid according to the W3C Trace Context specification.
+    pub fn new<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self::from_vec(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
+    /// Decodes a `tracestate` header string.
+    ///
+    /// Invalid and duplicated members are skipped, and at most 32 members are kept.
+    pub fn decode_w3c_tracestate(tracestate: &str) -> Self {
+        let mut entries: Vec<(String, String)> = Vec::new();
+        for member in tracestate.split(',') {
+            let member = member.trim_matches(|c| c == ' ' || c == '\t');
+            if member.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = member.split_once('=') else {
+                continue;
+            };
+            if !is_valid_key(key) || !is_valid_value(value) {
+                continue;
+            }
+            if entries.iter().any(|(k, _)| k == key) {
+                continue;
+            }
+            entries.push((key.to_string(), value.to_string()));
+            if entries.len() == MAX_MEMBERS {
+                break;
+            }
+        }
+        Self::from_vec(entries)
+    }
+
+    /// Encodes the `TraceState` into a `tracestate` header string.
+    pub fn encode_w3c_tracestate(&self) -> String {
+        let mut tracestate = String::new();
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                tracestate.push(',');
+            }
+            tracestate.push_str(key);
+            tracestate.push('=');
+            tracestate.push_str(value);
+        }
+        tracestate
+    }
+
+    /// Returns the value of the entry with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over the entries, in propagation order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .flat_map(|entries| entries.iter())
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns a `TraceState` with the entry set to `value` and moved to the front, as required
+    /// when a vendor updates its entry.
+    pub fn insert(&self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let key = key.into();
+        let mut entries = vec![(key.clone(), value.into())];
+        entries.extend(
+            self.iter()
+                .filter(|(k, _)| *k != key)
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        );
+        entries.truncate(MAX_MEMBERS);
+        Self::from_vec(entries)
+    }
+
+    /// Returns a `TraceState` without the entry with the given key.
+    pub fn remove(&self, key: &str) -> Self {
+        Self::from_vec(
+            self.iter()
+                .filter(|(k, _)| *k != key)
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    fn from_vec(entries: Vec<(String, String)>) -> Self {
+        if entries.is_empty() {
+            TraceState(None)
+        } else {
+            TraceState(Some(Arc::new(entries)))
+        }
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 256
+        && key.bytes().all(|b| {
+            b.is_ascii_lowercase()
+                || b.is_ascii_digit()
+                || matches!(b, b'_' | b'-' | b'*' | b'/' | b'@')
+        })
+}
+
+fn is_valid_value(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 256
+        && !value.ends_with(' ')
+        && value
+            .bytes()
+            .all(|b| (0x20..=0x7e).contains(&b) && b != b',' && b != b'=')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn w3c_tracestate() {
+        let trace_state =
+            TraceState::decode_w3c_tracestate("rojo=00f067aa0ba902b7 , congo=t61rcWkgMzE,,");
+        assert_eq!(trace_state.iter().collect::<Vec<_>>(), vec![
+            ("rojo", "00f067aa0ba902b7"),
+            ("congo", "t61rcWkgMzE")
+        ]);
+        assert_eq!(
+            trace_state.encode_w3c_tracestate(),
+            "rojo=00f067aa0ba902b7,congo=t61rcWkgMzE"
+        );
+
+        let trace_state =
+            TraceState::decode_w3c_tracestate("Invalid=1,valid=2,novalue,dup=1,dup=2,t@v=a=b");
+        assert_eq!(trace_state.encode_w3c_tracestate(), "valid=2,dup=1");
+
+        let tracestate = (0..40)
+            .map(|i| format!("k{i}=v"))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(
+            TraceState::decode_w3c_tracestate(&tracestate)
+                .iter()
+                .count(),
+            32
+        );
+
+        assert!(TraceState::decode_w3c_tracestate("").is_empty());
+        assert_eq!(TraceState::default().encode_w3c_tracestate(), "");
+    }
+
+    #[test]
+    fn update_tracestate() {
+        let trace_state = TraceState::new([("a", "1"), ("b", "2")]);
+        assert_eq!(
+            trace_state.insert("b", "3").encode_w3c_tracestate(),
+            "b=3,a=1"
+        );
+        assert_eq!(
+            trace_state.insert("c", "4").encode_w3c_tracestate(),
+            "c=4,a=1,b=2"
+        );
+        assert_eq!(trace_state.remove("a").encode_w3c_tracestate(), "b=2");
+        assert!(trace_state.remove("a").remove("b").is_empty());
+        assert_eq!(trace_state.get("a"), Some("1"));
+        assert_eq!(trace_state.get("c"), None);
+    }
+}
@@ -0,0 +1,297 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use crate::collector::SpanId;
+use crate::collector::SpanKind;
+use crate::collector::SpanRecord;
+use crate::collector::Status;
+use crate::collector::TraceId;
+use crate::collector::Value;
+use crate::collector::global_collector::Reporter;
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const MAGENTA: &str = "\x1b[35m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// A console reporter that prints span records to the stderr as indented trees, one per trace.
+///
+/// Each span is printed with its duration and its offset from the start of the trace, followed
+/// by its properties and events, and its children ordered by start time. Spans whose parent is
+/// not part of the same batch are printed as roots.
+///
+/// The output is colorized if the stderr is a terminal and the `NO_COLOR` environment variable
+/// is not set, unless overridden by [`with_color()`](TreeConsoleReporter::with_color).
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::collector::Config;
+/// use fastrace::collector::TreeConsoleReporter;
+///
+/// fastrace::set_reporter(TreeConsoleReporter::new(), Config::default());
+/// ```
+///
+/// prints traces like:
+///
+/// ```text
+/// trace 0af7651916cd43dd8448eb211c80319c
+/// └── request 12.41ms +0ns [server] method=GET
+///     ├── query 3.20ms +1.05ms ok db.rows=3
+///     │   ◆ retry +2.10ms attempt=2
+///     └── render 8.02ms +4.30ms error: template not found
+/// ```
+pub struct TreeConsoleReporter {
+    color: bool,
+}
+
+impl Default for TreeConsoleReporter {
+    fn default() -> Self {
+        TreeConsoleReporter {
+            color: std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
+
+impl TreeConsoleReporter {
+    /// Creates a `TreeConsoleReporter` that colorizes its output if the stderr is a terminal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the output is colorized with ANSI escape codes.
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    fn render(&self, spans: &[SpanRecord]) -> String {
+        let mut traces: HashMap<TraceId, Vec<&SpanRecord>> = HashMap::new();
+        for span in spans {
+            traces.entry(span.trace_id).or_default().push(span);
+        }
+        let mut traces: Vec<_> = traces.into_iter().collect();
+        for (_, spans) in &mut traces {
+            spans.sort_by_key(|span| (span.begin_time_unix_ns, span.span_id.0));
+        }
+        traces.sort_by_key(|(trace_id, spans)| (spans[0].begin_time_unix_ns, trace_id.0));
+
+        let mut out = String::new();
+        for (trace_id, spans) in traces {
+            let ids: HashSet<SpanId> = spans.iter().map(|span| span.span_id).collect();
+            let mut roots = vec![];
+            let mut children: HashMap<SpanId, Vec<&SpanRecord>> = HashMap::new();
+            for span in &spans {
+                if span.parent_id != span.span_id && ids.contains(&span.parent_id) {
+                    children.entry(span.parent_id).or_default().push(span);
+                } else {
+                    roots.push(*span);
+                }
+            }
+
+            let mut tree = Tree {
+                out: &mut out,
+                color: self.color,
+                children: &children,
+                start: spans[0].begin_time_unix_ns,
+            };
+            tree.paint(BOLD, format_args!("trace {trace_id}"));
+            tree.out.push('\n');
+            for (i, root) in roots.iter().enumerate() {
+                tree.span(root, "", i + 1 == roots.len());
+            }
+        }
+        out
+    }
+}
+
+impl Reporter for TreeConsoleReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        eprint!("{}", self.render(&spans));
+    }
+}
+
+struct Tree<'a> {
+    out: &'a mut String,
+    color: bool,
+    children: &'a HashMap<SpanId, Vec<&'a SpanRecord>>,
+    start: u64,
+}
+
+impl Tree<'_> {
+    fn span(&mut self, span: &SpanRecord, prefix: &str, last: bool) {
+        let connector = if last { "└── " } else { "├── " };
+        let _ = write!(self.out, "{prefix}{connector}");
+        self.paint(BOLD, &span.name);
+        self.out.push(' ');
+        self.paint(YELLOW, format_duration(span.duration_ns));
+        self.out.push(' ');
+        self.offset(span.begin_time_unix_ns);
+        if span.kind != SpanKind::Internal {
+            let _ = write!(self.out, " [{}]", span.kind.as_str());
+        }
+        match &span.status {
+            Status::Unset => {}
+            Status::Ok => {
+                self.out.push(' ');
+                self.paint(GREEN, "ok");
+            }
+            Status::Error(message) if message.is_empty() => {
+                self.out.push(' ');
+                self.paint(RED, "error");
+            }
+            Status::Error(message) => {
+                self.out.push(' ');
+                self.paint(RED, format_args!("error: {message}"));
+            }
+        }
+        self.properties(&span.properties);
+        self.out.push('\n');
+
+        let children = self
+            .children
+            .get(&span.span_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let child_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+        for event in &span.events {
+            let guide = if children.is_empty() { "" } else { "│   " };
+            let _ = write!(self.out, "{child_prefix}{guide}");
+            self.paint(MAGENTA, format_args!("◆ {}", event.name));
+            self.out.push(' ');
+            self.offset(event.timestamp_unix_ns);
+            self.properties(&event.properties);
+            self.out.push('\n');
+        }
+        for (i, child) in children.iter().enumerate() {
+            self.span(child, &child_prefix, i + 1 == children.len());
+        }
+    }
+
+    fn offset(&mut self, timestamp_unix_ns: u64) {
+        let offset = timestamp_unix_ns.saturating_sub(self.start);
+        self.paint(DIM, format_args!("+{}", format_duration(offset)));
+    }
+
+    fn properties(&mut self, properties: &[(Cow<'static, str>, Value)]) {
+        for (k, v) in properties {
+            self.out.push(' ');
+            self.paint(CYAN, k);
+            let _ = write!(self.out, "={v}");
+        }
+    }
+
+    fn paint(&mut self, style: &str, text: impl Display) {
+        if self.color {
+            let _ = write!(self.out, "{style}{text}{RESET}");
+        } else {
+            let _ = write!(self.out, "{text}");
+        }
+    }
+}
+
+fn format_duration(ns: u64) -> String {
+    if ns < 1_000 {
+        format!("{ns}ns")
+    } else {
+        format!("{:.2?}", Duration::from_nanos(ns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::EventRecord;
+
+    fn span(trace_id: u128, span_id: u64, parent_id: u64, begin: u64, name: &str) -> SpanRecord {
+        SpanRecord {
+            trace_id: TraceId(trace_id),
+            span_id: SpanId(span_id),
+            parent_id: SpanId(parent_id),
+            begin_time_unix_ns: begin,
+            duration_ns: 1_000_000,
+            name: name.to_string().into(),
+            ..SpanRecord::default()
+        }
+    }
+
+    #[test]
+    fn render_tree() {
+        let mut root = span(1, 1, 0, 1_000, "request");
+        root.kind = SpanKind::Server;
+        root.properties.push(("method".into(), "GET".into()));
+        let mut query = span(1, 2, 1, 2_000, "query");
+        query.status = Status::Ok;
+        query.events.push(EventRecord {
+            name: "retry".into(),
+            timestamp_unix_ns: 2_500,
+            properties: vec![("attempt".into(), 2i64.into())],
+        });
+        let mut render = span(1, 3, 1, 5_000, "render");
+        render.status = Status::Error("template not found".into());
+
+        let reporter = TreeConsoleReporter::new().with_color(false);
+        let output = reporter.render(&[
+            span(2, 1, 0, 500, "other"),
+            render,
+            span(1, 4, 2, 3_000, "fetch"),
+            query,
+            root,
+            span(2, 2, 9, 600, "orphan"),
+        ]);
+
+        assert_eq!(
+            output,
+            "\
+trace 00000000000000000000000000000002
+├── other 1.00ms +0ns
+└── orphan 1.00ms +100ns
+trace 00000000000000000000000000000001
+└── request 1.00ms +0ns [server] method=GET
+    ├── query 1.00ms +1.00µs ok
+    │   │   ◆ retry +1.50µs attempt=2
+    │   └── fetch 1.00ms +2.00µs
+    └── render 1.00ms +4.00µs error: template not found
+"
+        );
+    }
+
+    #[test]
+    fn render_color() {
+        let reporter = TreeConsoleReporter::new().with_color(true);
+        let output = reporter.render(&[span(1, 1, 0, 0, "root")]);
+
+        assert_eq!(
+            output,
+            "\x1b[1mtrace 00000000000000000000000000000001\x1b[0m\n└── \x1b[1mroot\x1b[0m \
+             \x1b[33m1.00ms\x1b[0m \x1b[2m+0ns\x1b[0m\n"
+        );
+    }
+}
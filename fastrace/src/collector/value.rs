@@ -0,0 +1,220 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// The value of a property.
+///
+/// Values are stored as they are given and formatted only by the reporters that need a string,
+/// so recording a number or a boolean does not allocate.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::prelude::*;
+///
+/// let root = Span::root("root", SpanContext::random())
+///     .with_property(|| ("http.method", "GET"))
+///     .with_property(|| ("http.status_code", 200))
+///     .with_property(|| ("cache.hit", true));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Value {
+    /// A string, converted from `&'static str`, `String` or `Cow<'static, str>`.
+    Str(Cow<'static, str>),
+    /// A signed integer, converted from any integer type that fits in an `i64`. Unsigned
+    /// integers that do not fit are converted to a [`Value::Str`].
+    I64(i64),
+    /// A floating-point number, converted from `f32` or `f64`.
+    F64(f64),
+    /// A boolean.
+    Bool(bool),
+    /// Raw bytes, formatted as lowercase hex by the reporters that need a string.
+    Bytes(Cow<'static, [u8]>),
+    /// A list of values, formatted as `[a, b, c]` by the reporters that need a string.
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// Returns the string if the value is a [`Value::Str`].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Str(s) => f.write_str(s),
+            Value::I64(v) => write!(f, "{v}"),
+            Value::F64(v) => write!(f, "{v}"),
+            Value::Bool(v) => write!(f, "{v}"),
+            Value::Bytes(bytes) => {
+                for byte in bytes.iter() {
+                    write!(f, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            Value::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+/// Serializes strings, numbers, booleans and arrays as such, and the other values, such as
+/// [`Value::Bytes`], as their [`Display`](fmt::Display) string.
+impl serde::Serialize for Value {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Str(s) => serializer.serialize_str(s),
+            Value::I64(v) => serializer.serialize_i64(*v),
+            Value::F64(v) => serializer.serialize_f64(*v),
+            Value::Bool(v) => serializer.serialize_bool(*v),
+            Value::Array(values) => serializer.collect_seq(values),
+            value => serializer.collect_str(value),
+        }
+    }
+}
+
+impl From<&'static str> for Value {
+    fn from(s: &'static str) -> Self {
+        Value::Str(Cow::Borrowed(s))
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(Cow::Owned(s))
+    }
+}
+
+impl From<Cow<'static, str>> for Value {
+    fn from(s: Cow<'static, str>) -> Self {
+        Value::Str(s)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    Value::I64(v as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, isize, u8, u16, u32);
+
+macro_rules! impl_from_large_uint {
+    ($($t:ty),*) => {
+        $(
+            /// Values not fitting in an `i64` are stored as strings.
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    match i64::try_from(v) {
+                        Ok(v) => Value::I64(v),
+                        Err(_) => Value::Str(Cow::Owned(v.to_string())),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_large_uint!(u64, usize);
+
+impl From<f32> for Value {
+    fn from(v: f32) -> Self {
+        Value::F64(v as f64)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::F64(v)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Bool(v)
+    }
+}
+
+impl From<&'static [u8]> for Value {
+    fn from(bytes: &'static [u8]) -> Self {
+        Value::Bytes(Cow::Borrowed(bytes))
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(bytes: Vec<u8>) -> Self {
+        Value::Bytes(Cow::Owned(bytes))
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(values: Vec<Value>) -> Self {
+        Value::Array(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_conversion() {
+        assert_eq!(Value::from("a"), Value::Str("a".into()));
+        assert_eq!(Value::from(42u8), Value::I64(42));
+        assert_eq!(Value::from(-42i32), Value::I64(-42));
+        assert_eq!(Value::from(42usize), Value::I64(42));
+        assert_eq!(
+            Value::from(u64::MAX),
+            Value::Str(u64::MAX.to_string().into())
+        );
+        assert_eq!(Value::from(0.5f32), Value::F64(0.5));
+        assert_eq!(Value::from(true), Value::Bool(true));
+        assert_eq!(Value::from(vec![1u8, 2]), Value::Bytes(vec![1, 2].into()));
+    }
+
+    #[test]
+    fn value_display() {
+        assert_eq!(Value::from("a").to_string(), "a");
+        assert_eq!(Value::from(-1).to_string(), "-1");
+        assert_eq!(Value::from(0.5).to_string(), "0.5");
+        assert_eq!(Value::from(false).to_string(), "false");
+        assert_eq!(Value::from(vec![0u8, 255]).to_string(), "00ff");
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::from("b")]).to_string(),
+            "[1, b]"
+        );
+    }
+}
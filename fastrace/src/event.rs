@@ -1,8 +1,10 @@
 // Copyright 2020 TiKV Project Authors. Licensed under Apache-2.0.
 
 use std::borrow::Cow;
+use std::error::Error;
 
 use crate::Span;
+use crate::collector::Value;
 use crate::local::LocalSpan;
 use crate::util::Properties;
 
@@ -45,7 +47,7 @@ impl Event {
     pub fn with_property<K, V, F>(self, property: F) -> Self
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         F: FnOnce() -> (K, V),
     {
         self.with_properties(|| [property()])
@@ -64,7 +66,7 @@ impl Event {
     pub fn with_properties<K, V, I, F>(mut self, properties: F) -> Self
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -77,6 +79,21 @@ impl Event {
         self
     }
 
+    /// Create an `exception` event describing `error` and its chain of sources.
+    pub(crate) fn from_error(error: &dyn Error) -> Self {
+        let sources = std::iter::successors(error.source(), |&source| source.source());
+        Event::new("exception").with_properties(|| {
+            std::iter::once((Cow::Borrowed("exception.message"), error.to_string())).chain(
+                sources.enumerate().map(|(i, source)| {
+                    (
+                        Cow::Owned(format!("exception.source.{i}")),
+                        source.to_string(),
+                    )
+                }),
+            )
+        })
+    }
+
     /// Adds an event to the parent span with the given name and properties.
     ///
     /// # Examples
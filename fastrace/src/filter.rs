@@ -0,0 +1,308 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+
+use crate::level::Level;
+use crate::level::level_enabled;
+
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+
+/// The callsites that have cached a decision, to be reset whenever the filter or the max level
+/// changes.
+static CALLSITES: Mutex<Vec<&'static Callsite>> = Mutex::new(Vec::new());
+
+const UNREGISTERED: usize = 0;
+const STALE: usize = 1;
+const DISABLED: usize = 2;
+const ENABLED: usize = 3;
+
+/// The environment variable read by [`Filter::from_env()`].
+pub const FILTER_ENV: &str = "FASTRACE_FILTER";
+
+/// A set of directives deciding which spans created by [`#[trace]`](crate::trace) are recorded.
+///
+/// A filter is parsed from a comma-separated list of directives such as
+/// `my_crate::db=off,my_crate=debug,warn`. Each directive is either:
+///
+/// - `target=value`, which applies to the functions in the module `target` or its submodules, and
+///   to the spans whose name is `target` or starts with `target::`,
+/// - `value`, which applies to every span,
+/// - `target`, which is the same as `target=on`.
+///
+/// The value is `on`, `off`, or a [`Level`], in which case a span is recorded only if its level
+/// is not more verbose. Spans without a level are considered to be at [`Level::Info`].
+///
+/// The directive with the longest matching target applies. If no directive matches, the span is
+/// recorded according to [`max_level()`](crate::max_level).
+///
+/// Only the spans created by [`#[trace]`](crate::trace) are filtered. The spans created by hand,
+/// such as with [`Span::enter_with_local_parent()`](crate::Span::enter_with_local_parent) or
+/// [`LocalSpan::enter_with_local_parent()`](crate::local::LocalSpan::enter_with_local_parent),
+/// are always recorded, even if named with [`func_path!()`](crate::func_path).
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::Filter;
+///
+/// let filter: Filter = "my_crate::db=off,my_crate=on".parse().unwrap();
+/// fastrace::set_filter(filter);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    directives: Vec<Directive>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Directive {
+    target: Option<String>,
+    value: DirectiveValue,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DirectiveValue {
+    Off,
+    On,
+    Level(Level),
+}
+
+impl DirectiveValue {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(DirectiveValue::Off),
+            "on" => Some(DirectiveValue::On),
+            s => s.parse().ok().map(DirectiveValue::Level),
+        }
+    }
+}
+
+impl Filter {
+    /// Parses a filter from the `FASTRACE_FILTER` environment variable.
+    ///
+    /// Returns an empty filter, which has no effect, if the variable is not set.
+    pub fn from_env() -> Result<Self, ParseFilterError> {
+        match std::env::var(FILTER_ENV) {
+            Ok(directives) => directives.parse(),
+            Err(_) => Ok(Filter::default()),
+        }
+    }
+
+    /// Returns whether a span is recorded, or `None` if no directive applies to it.
+    fn decide(&self, module_path: &str, name: &str, level: Option<Level>) -> Option<bool> {
+        let directive = self
+            .directives
+            .iter()
+            .filter(|directive| match &directive.target {
+                Some(target) => is_prefix(target, module_path) || is_prefix(target, name),
+                None => true,
+            })
+            .max_by_key(|directive| directive.target.as_ref().map_or(0, |target| target.len()))?;
+
+        Some(match directive.value {
+            DirectiveValue::Off => false,
+            DirectiveValue::On => true,
+            DirectiveValue::Level(max) => level.unwrap_or(Level::Info) <= max,
+        })
+    }
+}
+
+/// Returns whether `path` is `target` or one of its submodules.
+fn is_prefix(target: &str, path: &str) -> bool {
+    path.strip_prefix(target)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+impl FromStr for Filter {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut directives = Vec::new();
+        for directive in s.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let directive = match directive.split_once('=') {
+                Some((target, value)) => Directive {
+                    target: Some(target.trim().to_string()),
+                    value: DirectiveValue::parse(value.trim())
+                        .ok_or_else(|| ParseFilterError(directive.to_string()))?,
+                },
+                None => match DirectiveValue::parse(directive) {
+                    Some(value) => Directive {
+                        target: None,
+                        value,
+                    },
+                    None => Directive {
+                        target: Some(directive.to_string()),
+                        value: DirectiveValue::On,
+                    },
+                },
+            };
+            if directive.target.as_deref() == Some("") {
+                return Err(ParseFilterError(s.to_string()));
+            }
+            directives.push(directive);
+        }
+        Ok(Filter { directives })
+    }
+}
+
+/// The error returned when parsing an invalid [`Filter`] directive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseFilterError(String);
+
+impl fmt::Display for ParseFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid filter directive `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseFilterError {}
+
+/// Sets the global filter consulted by the spans created by [`#[trace]`](crate::trace).
+///
+/// The decision for each annotated function is cached, and only evaluated again after the filter
+/// or the [max level](crate::set_max_level) changes.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::Filter;
+///
+/// fastrace::set_filter(Filter::from_env().unwrap());
+/// ```
+pub fn set_filter(filter: Filter) {
+    *FILTER.write() = Some(filter);
+    invalidate_callsites();
+}
+
+pub(crate) fn invalidate_callsites() {
+    for callsite in CALLSITES.lock().iter() {
+        callsite.state.store(STALE, Ordering::Relaxed);
+    }
+}
+
+/// The cached filter decision of a function annotated with [`#[trace]`](crate::trace).
+#[doc(hidden)]
+pub struct Callsite {
+    module_path: &'static str,
+    level: Option<Level>,
+    /// One of `UNREGISTERED`, `STALE`, `DISABLED` or `ENABLED`.
+    state: AtomicUsize,
+}
+
+impl Callsite {
+    pub const fn new(module_path: &'static str, level: Option<Level>) -> Self {
+        Callsite {
+            module_path,
+            level,
+            state: AtomicUsize::new(UNREGISTERED),
+        }
+    }
+
+    /// Returns whether the span named `name` is recorded.
+    #[inline]
+    pub fn enabled(&'static self, name: &str) -> bool {
+        match self.state.load(Ordering::Relaxed) {
+            ENABLED => true,
+            DISABLED => false,
+            _ => self.refresh(name),
+        }
+    }
+
+    #[cold]
+    fn refresh(&'static self, name: &str) -> bool {
+        // Holding the lock while deciding keeps `invalidate_callsites()` from running in between,
+        // which would leave a decision based on the previous filter in place.
+        let mut callsites = CALLSITES.lock();
+        if self.state.load(Ordering::Relaxed) == UNREGISTERED {
+            callsites.push(self);
+        }
+        let enabled = FILTER
+            .read()
+            .as_ref()
+            .and_then(|filter| filter.decide(self.module_path, name, self.level))
+            .unwrap_or_else(|| self.level.map_or(true, level_enabled));
+        let state = if enabled { ENABLED } else { DISABLED };
+        self.state.store(state, Ordering::Relaxed);
+        enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_filter() {
+        let filter: Filter = " my_crate::db=off, my_crate = debug ,warn,other"
+            .parse()
+            .unwrap();
+        assert_eq!(filter.directives, vec![
+            Directive {
+                target: Some("my_crate::db".to_string()),
+                value: DirectiveValue::Off,
+            },
+            Directive {
+                target: Some("my_crate".to_string()),
+                value: DirectiveValue::Level(Level::Debug),
+            },
+            Directive {
+                target: None,
+                value: DirectiveValue::Level(Level::Warn),
+            },
+            Directive {
+                target: Some("other".to_string()),
+                value: DirectiveValue::On,
+            },
+        ]);
+
+        assert_eq!(
+            "my_crate=verbose".parse::<Filter>(),
+            Err(ParseFilterError("my_crate=verbose".to_string()))
+        );
+        assert!("=on".parse::<Filter>().is_err());
+        assert_eq!("".parse::<Filter>(), Ok(Filter::default()));
+    }
+
+    #[test]
+    fn decide() {
+        let filter: Filter = "my_crate::db=off,my_crate=debug,warn,query"
+            .parse()
+            .unwrap();
+
+        assert_eq!(filter.decide("my_crate::db", "f", None), Some(false));
+        assert_eq!(filter.decide("my_crate::db::pool", "f", None), Some(false));
+        assert_eq!(filter.decide("my_crate::dbx", "f", None), Some(true));
+        assert_eq!(
+            filter.decide("my_crate", "f", Some(Level::Debug)),
+            Some(true)
+        );
+        assert_eq!(
+            filter.decide("my_crate", "f", Some(Level::Trace)),
+            Some(false)
+        );
+        assert_eq!(filter.decide("other", "f", None), Some(false));
+        assert_eq!(filter.decide("other", "f", Some(Level::Warn)), Some(true));
+        assert_eq!(filter.decide("other", "query", None), Some(true));
+        assert_eq!(filter.decide("other", "query::all", None), Some(true));
+
+        let filter: Filter = "my_crate=off".parse().unwrap();
+        assert_eq!(filter.decide("other", "f", None), None);
+    }
+}
@@ -31,11 +31,27 @@
 //! [`enter_on_poll()`]:(FutureExt::enter_on_poll)
 
 use std::borrow::Cow;
+use std::pin::Pin;
 use std::task::Poll;
+use std::time::Duration;
+
+use fastant::Instant;
 
 use crate::Span;
 use crate::local::LocalSpan;
 
+/// The property recording the total time, in nanoseconds, spent polling a future with
+/// [poll stats](InSpan::with_poll_stats).
+pub const POLL_BUSY_NS_PROPERTY: &str = "poll.busy_ns";
+
+/// The property recording the total time, in nanoseconds, a future with
+/// [poll stats](InSpan::with_poll_stats) spent waiting between polls.
+pub const POLL_IDLE_NS_PROPERTY: &str = "poll.idle_ns";
+
+/// The property recording the number of times a future with
+/// [poll stats](InSpan::with_poll_stats) was polled.
+pub const POLL_COUNT_PROPERTY: &str = "poll.count";
+
 impl<T: std::future::Future> FutureExt for T {}
 
 /// An extension trait for `Futures` that provides tracing instrument adapters.
@@ -69,6 +85,7 @@ pub trait FutureExt: std::future::Future + Sized {
         InSpan {
             inner: self,
             span: Some(span),
+            poll_stats: None,
         }
     }
 
@@ -107,11 +124,42 @@ pub trait FutureExt: std::future::Future + Sized {
 }
 
 /// Adapter for [`FutureExt::in_span()`](FutureExt::in_span).
-#[pin_project::pin_project]
+#[pin_project::pin_project(PinnedDrop)]
 pub struct InSpan<T> {
     #[pin]
     inner: T,
     span: Option<Span>,
+    poll_stats: Option<PollStats>,
+}
+
+impl<T> InSpan<T> {
+    /// Accumulates the time spent in polls, the time spent waiting between polls and the
+    /// number of polls, and records them on the span when the future completes or is dropped,
+    /// as the [`POLL_BUSY_NS_PROPERTY`], [`POLL_IDLE_NS_PROPERTY`] and [`POLL_COUNT_PROPERTY`]
+    /// properties.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use fastrace::prelude::*;
+    ///
+    /// let root = Span::root("Root", SpanContext::random());
+    /// let task = async {
+    ///     // ...
+    /// }
+    /// .in_span(Span::enter_with_parent("Task", &root))
+    /// .with_poll_stats();
+    ///
+    /// tokio::spawn(task);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_poll_stats(mut self) -> Self {
+        self.poll_stats = Some(PollStats::default());
+        self
+    }
 }
 
 impl<T: std::future::Future> std::future::Future for InSpan<T> {
@@ -121,18 +169,69 @@ impl<T: std::future::Future> std::future::Future for InSpan<T> {
         let this = self.project();
 
         let _guard = this.span.as_ref().map(|s| s.set_local_parent());
-        let res = this.inner.poll(cx);
+        let res = match this.poll_stats {
+            Some(poll_stats) => poll_stats.measure(|| this.inner.poll(cx)),
+            None => this.inner.poll(cx),
+        };
 
         match res {
             r @ Poll::Pending => r,
             other => {
-                this.span.take();
+                if let (Some(span), Some(poll_stats)) = (this.span.take(), this.poll_stats) {
+                    poll_stats.record(&span);
+                }
                 other
             }
         }
     }
 }
 
+#[pin_project::pinned_drop]
+impl<T> PinnedDrop for InSpan<T> {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        if let (Some(span), Some(poll_stats)) = (this.span.as_ref(), this.poll_stats.as_ref()) {
+            poll_stats.record(span);
+        }
+    }
+}
+
+/// Poll accounting of a traced future, stream or sink.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct PollStats {
+    busy: Duration,
+    count: u64,
+}
+
+impl PollStats {
+    /// Runs a poll, adding its duration to the busy time.
+    #[inline]
+    pub fn measure<R>(&mut self, poll: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let res = poll();
+        self.busy += start.elapsed();
+        self.count += 1;
+        res
+    }
+
+    /// Records the stats as properties of `span`. The idle time is the lifetime of the span
+    /// that was not spent in polls.
+    pub fn record(&self, span: &Span) {
+        let idle = span
+            .elapsed()
+            .map(|elapsed| elapsed.saturating_sub(self.busy))
+            .unwrap_or_default();
+        span.add_properties(|| {
+            [
+                (POLL_BUSY_NS_PROPERTY, self.busy.as_nanos() as i64),
+                (POLL_IDLE_NS_PROPERTY, idle.as_nanos() as i64),
+                (POLL_COUNT_PROPERTY, self.count as i64),
+            ]
+        });
+    }
+}
+
 /// Adapter for [`FutureExt::enter_on_poll()`](FutureExt::enter_on_poll).
 #[pin_project::pin_project]
 pub struct EnterOnPoll<T> {
@@ -0,0 +1,141 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(Level::Info as usize);
+
+/// The verbosity level of a span created by [`#[trace(level = "...")]`](crate::trace).
+///
+/// Levels are ordered from the least verbose, [`Level::Error`], to the most verbose,
+/// [`Level::Trace`]. A span is created only if its level is not more verbose than
+/// [`max_level()`], unless a [`Filter`](crate::Filter) directive applies to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(usize)]
+pub enum Level {
+    /// Spans of failing operations only.
+    Error = 1,
+    /// Spans of operations that may lead to failures.
+    Warn,
+    /// Spans of the main operations of the application.
+    Info,
+    /// Detailed spans, useful when diagnosing an issue.
+    Debug,
+    /// Very detailed spans, such as those of hot loops.
+    Trace,
+}
+
+impl Level {
+    /// Returns the lowercase name of the level, e.g. `"debug"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    fn from_usize(level: usize) -> Level {
+        match level {
+            1 => Level::Error,
+            2 => Level::Warn,
+            3 => Level::Info,
+            4 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The error returned when parsing an unknown [`Level`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseLevelError(());
+
+impl fmt::Display for ParseLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected one of `error`, `warn`, `info`, `debug` or `trace`")
+    }
+}
+
+impl std::error::Error for ParseLevelError {}
+
+impl FromStr for Level {
+    type Err = ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ]
+        .into_iter()
+        .find(|level| level.as_str().eq_ignore_ascii_case(s))
+        .ok_or(ParseLevelError(()))
+    }
+}
+
+/// Sets the most verbose level of the spans created by
+/// [`#[trace(level = "...")]`](crate::trace). Defaults to [`Level::Info`].
+///
+/// Functions annotated without a `level` are always traced. A [`Filter`](crate::Filter) can
+/// override the max level for some modules.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::Level;
+///
+/// fastrace::set_max_level(Level::Debug);
+/// assert_eq!(fastrace::max_level(), Level::Debug);
+/// ```
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as usize, Ordering::Relaxed);
+    crate::filter::invalidate_callsites();
+}
+
+/// Returns the most verbose level of the spans created by
+/// [`#[trace(level = "...")]`](crate::trace).
+pub fn max_level() -> Level {
+    Level::from_usize(MAX_LEVEL.load(Ordering::Relaxed))
+}
+
+#[inline]
+pub(crate) fn level_enabled(level: Level) -> bool {
+    level as usize <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_level() {
+        assert_eq!("debug".parse(), Ok(Level::Debug));
+        assert_eq!("WARN".parse(), Ok(Level::Warn));
+        assert_eq!("verbose".parse::<Level>(), Err(ParseLevelError(())));
+        assert!(Level::Error < Level::Trace);
+    }
+}
@@ -67,7 +67,8 @@
 //!
 //! Applications should initialize a [`Reporter`] implementation early in the program's runtime.
 //! Span records generated before the reporter is initialized will be ignored. Before
-//! terminating, [`flush()`] should be called to ensure all collected span records are reported.
+//! terminating, [`flush()`] should be called to ensure all collected span records are reported,
+//! or [`shutdown()`] if the reporter also needs to flush its own buffers.
 //!
 //! ```
 //! use fastrace::collector::Config;
@@ -301,7 +302,8 @@
 //!
 //! For an easy start, `fastrace` offers a [`ConsoleReporter`] that prints span
 //! records to stderr. For more advanced use, crates like `fastrace-jaeger`, `fastrace-datadog`,
-//! and `fastrace-opentelemetry` are available.
+//! and `fastrace-opentelemetry` are available. To send span records to several backends at once,
+//! wrap the reporters in a [`FanoutReporter`].
 //!
 //! The reporter runs in a background collector thread. [`Config::report_interval()`] controls the
 //! *maximum* interval between report cycles, but the reporter may be invoked earlier. Do not rely
@@ -358,6 +360,7 @@
 //! [`Event`]: crate::Event
 //! [`Reporter`]: crate::collector::Reporter
 //! [`ConsoleReporter`]: crate::collector::ConsoleReporter
+//! [`FanoutReporter`]: crate::collector::FanoutReporter
 //! [`Config`]: crate::collector::Config
 //! [`Config::report_interval()`]: crate::collector::Config::report_interval
 //! [`Future`]: std::future::Future
@@ -372,9 +375,12 @@
 
 pub mod collector;
 mod event;
+mod filter;
 pub mod future;
+mod level;
 pub mod local;
 mod macros;
+pub mod propagation;
 mod span;
 #[doc(hidden)]
 pub mod util;
@@ -383,7 +389,19 @@ pub use fastrace_macro::trace;
 
 pub use crate::collector::global_collector::flush;
 pub use crate::collector::global_collector::set_reporter;
+pub use crate::collector::global_collector::shutdown;
+pub use crate::collector::sampler::set_sampler;
 pub use crate::event::Event;
+#[doc(hidden)]
+pub use crate::filter::Callsite;
+pub use crate::filter::FILTER_ENV;
+pub use crate::filter::Filter;
+pub use crate::filter::ParseFilterError;
+pub use crate::filter::set_filter;
+pub use crate::level::Level;
+pub use crate::level::ParseLevelError;
+pub use crate::level::max_level;
+pub use crate::level::set_max_level;
 pub use crate::span::Span;
 
 pub mod prelude {
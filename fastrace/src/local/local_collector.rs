@@ -9,6 +9,7 @@ use fastant::Instant;
 use crate::local::local_span_stack::LOCAL_SPAN_STACK;
 use crate::local::local_span_stack::LocalSpanStack;
 use crate::local::local_span_stack::SpanLineHandle;
+use crate::local::thread_info::ThreadInfo;
 use crate::prelude::SpanContext;
 use crate::prelude::SpanRecord;
 use crate::util::CollectToken;
@@ -96,6 +97,7 @@ pub struct LocalSpans {
 pub struct LocalSpansInner {
     pub spans: RawSpans,
     pub end_time: Instant,
+    pub thread: ThreadInfo,
 }
 
 impl LocalCollector {
@@ -148,7 +150,7 @@ impl LocalCollector {
     }
 
     pub(crate) fn collect_spans_and_token(mut self) -> (LocalSpansInner, Option<CollectToken>) {
-        let (spans, collect_token) = self
+        let (spans, collect_token, thread) = self
             .inner
             .take()
             .and_then(
@@ -157,7 +159,9 @@ impl LocalCollector {
                      span_line_handle,
                  }| {
                     let s = &mut (*stack).borrow_mut();
+                    let thread = s.thread().clone();
                     s.unregister_and_collect(span_line_handle)
+                        .map(|(spans, collect_token)| (spans, collect_token, thread))
                 },
             )
             .unwrap_or_default();
@@ -166,6 +170,7 @@ impl LocalCollector {
             LocalSpansInner {
                 spans,
                 end_time: Instant::now(),
+                thread,
             },
             collect_token,
         )
@@ -258,8 +263,9 @@ mod tests {
                 collect_id: 42,
                 is_root: false,
                 is_sampled: true,
+                ..Default::default()
             };
-            let collector2 = LocalCollector::new(Some(token2.into()), stack.clone());
+            let collector2 = LocalCollector::new(Some(token2.clone().into()), stack.clone());
             let span2 = stack.borrow_mut().enter_span("span2").unwrap();
             let span3 = stack.borrow_mut().enter_span("span3").unwrap();
             stack.borrow_mut().exit_span(span3);
@@ -298,8 +304,9 @@ span1 []
                 collect_id: 42,
                 is_root: false,
                 is_sampled: true,
+                ..Default::default()
             };
-            let collector2 = LocalCollector::new(Some(token2.into()), stack.clone());
+            let collector2 = LocalCollector::new(Some(token2.clone().into()), stack.clone());
             let span2 = stack.borrow_mut().enter_span("span2").unwrap();
             let span3 = stack.borrow_mut().enter_span("span3").unwrap();
             stack.borrow_mut().exit_span(span3);
@@ -6,6 +6,9 @@ use std::fmt;
 use std::rc::Rc;
 
 use crate::Event;
+use crate::collector::SpanKind;
+use crate::collector::Status;
+use crate::collector::Value;
 use crate::local::local_span_line::LocalSpanHandle;
 use crate::local::local_span_stack::LOCAL_SPAN_STACK;
 use crate::local::local_span_stack::LocalSpanStack;
@@ -78,7 +81,7 @@ impl LocalSpan {
     pub fn with_property<K, V, F>(self, property: F) -> Self
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         F: FnOnce() -> (K, V),
     {
         self.with_properties(|| [property()])
@@ -98,7 +101,7 @@ impl LocalSpan {
     pub fn with_properties<K, V, I, F>(self, properties: F) -> Self
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -111,6 +114,29 @@ impl LocalSpan {
         self
     }
 
+    /// Sets the kind of the `LocalSpan` and returns the modified `LocalSpan`.
+    ///
+    /// Spans are [`SpanKind::Internal`] by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::SpanKind;
+    /// use fastrace::prelude::*;
+    ///
+    /// let span = LocalSpan::enter_with_local_parent("SELECT").with_kind(SpanKind::Client);
+    /// ```
+    #[inline]
+    pub fn with_kind(self, kind: SpanKind) -> Self {
+        #[cfg(feature = "enable")]
+        if let Some(LocalSpanInner { stack, span_handle }) = &self.inner {
+            let span_stack = &mut *stack.borrow_mut();
+            span_stack.with_kind(span_handle, kind);
+        }
+
+        self
+    }
+
     /// Add a single property to the current local parent.
     ///
     /// A property is an arbitrary key-value pair associated with a span.
@@ -128,7 +154,7 @@ impl LocalSpan {
     pub fn add_property<K, V, F>(property: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         F: FnOnce() -> (K, V),
     {
         Self::add_properties(|| [property()])
@@ -149,7 +175,7 @@ impl LocalSpan {
     pub fn add_properties<K, V, I, F>(properties: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -165,6 +191,56 @@ impl LocalSpan {
         }
     }
 
+    /// Sets the status of the current local parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::Status;
+    /// use fastrace::prelude::*;
+    ///
+    /// LocalSpan::set_status(Status::Error("connection refused".into()));
+    /// ```
+    #[inline]
+    pub fn set_status(status: Status) {
+        #[cfg(feature = "enable")]
+        {
+            LOCAL_SPAN_STACK
+                .try_with(|stack| stack.borrow_mut().set_status(status))
+                .ok();
+        }
+    }
+
+    /// Records an error on the current local parent.
+    ///
+    /// The status of the span is set to [`Status::Error`], and an `exception` event is added
+    /// with the message of the error and of each of its sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::prelude::*;
+    ///
+    /// if let Err(err) = std::fs::read("/nonexistent") {
+    ///     LocalSpan::record_error(&err);
+    /// }
+    /// ```
+    #[inline]
+    pub fn record_error(error: &dyn std::error::Error) {
+        #[cfg(feature = "enable")]
+        {
+            LOCAL_SPAN_STACK
+                .try_with(|stack| {
+                    let mut stack = stack.borrow_mut();
+                    if stack.current_span_line().is_some() {
+                        stack.add_event(Event::from_error(error));
+                        stack.set_status(Status::Error(error.to_string().into()));
+                    }
+                })
+                .ok();
+        }
+    }
+
     /// Adds an event to the `LocalSpan`.
     ///
     /// # Examples
@@ -233,8 +309,9 @@ mod tests {
             collect_id: 42,
             is_root: false,
             is_sampled: true,
+            ..Default::default()
         };
-        let collector = LocalCollector::new(Some(token.into()), stack.clone());
+        let collector = LocalCollector::new(Some(token.clone().into()), stack.clone());
 
         {
             let _g = LocalSpan::enter_with_stack("span1", stack.clone());
@@ -271,8 +348,9 @@ span1 []
             collect_id: 42,
             is_root: false,
             is_sampled: true,
+            ..Default::default()
         };
-        let collector = LocalCollector::new(Some(token.into()), stack.clone());
+        let collector = LocalCollector::new(Some(token.clone().into()), stack.clone());
 
         {
             let span1 = LocalSpan::enter_with_stack("span1", stack.clone());
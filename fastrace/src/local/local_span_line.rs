@@ -4,6 +4,9 @@ use std::borrow::Cow;
 
 use crate::Event;
 use crate::collector::CollectTokenItem;
+use crate::collector::SpanKind;
+use crate::collector::Status;
+use crate::collector::Value;
 use crate::local::span_queue::SpanHandle;
 use crate::local::span_queue::SpanQueue;
 use crate::util::CollectToken;
@@ -72,7 +75,7 @@ impl SpanLine {
     pub fn add_properties<K, V, I, F>(&mut self, properties: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -83,11 +86,20 @@ impl SpanLine {
         self.span_queue.add_properties(properties());
     }
 
+    #[inline]
+    pub fn set_status(&mut self, status: Status) {
+        if !self.is_sampled {
+            return;
+        }
+
+        self.span_queue.set_status(status);
+    }
+
     #[inline]
     pub fn with_properties<K, V, I, F>(&mut self, handle: &LocalSpanHandle, properties: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -101,6 +113,17 @@ impl SpanLine {
         }
     }
 
+    #[inline]
+    pub fn with_kind(&mut self, handle: &LocalSpanHandle, kind: SpanKind) {
+        if !self.is_sampled {
+            return;
+        }
+
+        if self.epoch == handle.span_line_epoch {
+            self.span_queue.with_kind(&handle.span_handle, kind);
+        }
+    }
+
     #[inline]
     pub fn current_collect_token(&self) -> Option<CollectToken> {
         self.collect_token.as_ref().map(|collect_token| {
@@ -115,6 +138,8 @@ impl SpanLine {
                     collect_id: item.collect_id,
                     is_root: item.is_root,
                     is_sampled: item.is_sampled,
+                    trace_state: item.trace_state.clone(),
+                    baggage: item.baggage.clone(),
                 })
                 .collect()
         })
@@ -175,6 +200,7 @@ span1 []
             collect_id: 42,
             is_root: false,
             is_sampled: true,
+            ..Default::default()
         };
         let token2 = CollectTokenItem {
             trace_id: TraceId(1235),
@@ -182,12 +208,13 @@ span1 []
             collect_id: 43,
             is_root: false,
             is_sampled: true,
+            ..Default::default()
         };
-        let token = [token1, token2].into_iter().collect();
+        let token = [token1.clone(), token2.clone()].into_iter().collect();
         let mut span_line = SpanLine::new(16, 1, Some(token));
 
         let current_token = span_line.current_collect_token().unwrap();
-        assert_eq!(current_token.as_slice(), &[token1, token2]);
+        assert_eq!(current_token.as_slice(), &[token1.clone(), token2.clone()]);
 
         let span = span_line.start_span("span").unwrap();
         let current_token = span_line.current_collect_token().unwrap();
@@ -199,6 +226,7 @@ span1 []
                 collect_id: 42,
                 is_root: false,
                 is_sampled: true,
+                ..Default::default()
             },
             CollectTokenItem {
                 trace_id: TraceId(1235),
@@ -206,15 +234,19 @@ span1 []
                 collect_id: 43,
                 is_root: false,
                 is_sampled: true,
+                ..Default::default()
             }
         ]);
         span_line.finish_span(span);
 
         let current_token = span_line.current_collect_token().unwrap();
-        assert_eq!(current_token.as_slice(), &[token1, token2]);
+        assert_eq!(current_token.as_slice(), &[token1.clone(), token2.clone()]);
 
         let (spans, collect_token) = span_line.collect(1).unwrap();
-        assert_eq!(collect_token.unwrap().as_slice(), &[token1, token2]);
+        assert_eq!(collect_token.unwrap().as_slice(), &[
+            token1.clone(),
+            token2.clone()
+        ]);
         assert_eq!(
             tree_str_from_raw_spans(spans),
             r#"
@@ -250,8 +282,9 @@ span []
             collect_id: 42,
             is_root: false,
             is_sampled: true,
+            ..Default::default()
         };
-        let mut span_line1 = SpanLine::new(16, 1, Some(item.into()));
+        let mut span_line1 = SpanLine::new(16, 1, Some(item.clone().into()));
         let mut span_line2 = SpanLine::new(16, 2, None);
         assert_eq!(span_line1.span_line_epoch(), 1);
         assert_eq!(span_line2.span_line_epoch(), 2);
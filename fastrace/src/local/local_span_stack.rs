@@ -5,8 +5,12 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::Event;
+use crate::collector::SpanKind;
+use crate::collector::Status;
+use crate::collector::Value;
 use crate::local::local_span_line::LocalSpanHandle;
 use crate::local::local_span_line::SpanLine;
+use crate::local::thread_info::ThreadInfo;
 use crate::util::CollectToken;
 use crate::util::RawSpans;
 
@@ -21,6 +25,7 @@ pub struct LocalSpanStack {
     span_lines: Vec<SpanLine>,
     capacity: usize,
     next_span_line_epoch: usize,
+    thread: ThreadInfo,
 }
 
 impl LocalSpanStack {
@@ -30,6 +35,7 @@ impl LocalSpanStack {
             span_lines: Vec::with_capacity(capacity / 8),
             capacity,
             next_span_line_epoch: 0,
+            thread: ThreadInfo::current(),
         }
     }
 
@@ -98,7 +104,7 @@ impl LocalSpanStack {
     pub fn add_properties<K, V, I, F>(&mut self, properties: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -107,6 +113,13 @@ impl LocalSpanStack {
         }
     }
 
+    #[inline]
+    pub fn set_status(&mut self, status: Status) {
+        if let Some(span_line) = self.current_span_line() {
+            span_line.set_status(status);
+        }
+    }
+
     #[inline]
     pub fn with_properties<K, V, I, F>(
         &mut self,
@@ -114,7 +127,7 @@ impl LocalSpanStack {
         properties: F,
     ) where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -128,11 +141,29 @@ impl LocalSpanStack {
         }
     }
 
+    #[inline]
+    pub fn with_kind(&mut self, local_span_handle: &LocalSpanHandle, kind: SpanKind) {
+        debug_assert!(self.current_span_line().is_some());
+        if let Some(span_line) = self.current_span_line() {
+            debug_assert_eq!(
+                span_line.span_line_epoch(),
+                local_span_handle.span_line_epoch
+            );
+            span_line.with_kind(local_span_handle, kind);
+        }
+    }
+
     pub fn current_collect_token(&mut self) -> Option<CollectToken> {
         let span_line = self.current_span_line()?;
         span_line.current_collect_token()
     }
 
+    /// Returns the thread the span stack belongs to.
+    #[inline]
+    pub fn thread(&self) -> &ThreadInfo {
+        &self.thread
+    }
+
     #[inline]
     pub fn current_span_line(&mut self) -> Option<&mut SpanLine> {
         self.span_lines.last_mut()
@@ -161,8 +192,11 @@ mod tests {
             collect_id: 42,
             is_root: false,
             is_sampled: true,
+            ..Default::default()
         };
-        let span_line1 = span_stack.register_span_line(Some(token1.into())).unwrap();
+        let span_line1 = span_stack
+            .register_span_line(Some(token1.clone().into()))
+            .unwrap();
         {
             {
                 let span1 = span_stack.enter_span("span1").unwrap();
@@ -179,8 +213,11 @@ mod tests {
                 collect_id: 48,
                 is_root: false,
                 is_sampled: true,
+                ..Default::default()
             };
-            let span_line2 = span_stack.register_span_line(Some(token2.into())).unwrap();
+            let span_line2 = span_stack
+                .register_span_line(Some(token2.clone().into()))
+                .unwrap();
             {
                 let span3 = span_stack.enter_span("span3").unwrap();
                 {
@@ -228,6 +265,7 @@ span1 []
                             collect_id: 42,
                             is_root: false,
                             is_sampled: true,
+                            ..Default::default()
                         }
                         .into(),
                     ))
@@ -244,6 +282,7 @@ span1 []
                                         collect_id: 43,
                                         is_root: false,
                                         is_sampled: true,
+                                        ..Default::default()
                                     }
                                     .into()
                                 ))
@@ -265,6 +304,7 @@ span1 []
                                         collect_id: 44,
                                         is_root: false,
                                         is_sampled: true,
+                                        ..Default::default()
                                     }
                                     .into()
                                 ))
@@ -291,10 +331,13 @@ span1 []
             collect_id: 1,
             is_root: false,
             is_sampled: true,
+            ..Default::default()
         };
-        let span_line1 = span_stack.register_span_line(Some(token1.into())).unwrap();
+        let span_line1 = span_stack
+            .register_span_line(Some(token1.clone().into()))
+            .unwrap();
         assert_eq!(span_stack.current_collect_token().unwrap().as_slice(), &[
-            token1
+            token1.clone()
         ]);
         {
             let span_line2 = span_stack.register_span_line(None).unwrap();
@@ -306,8 +349,11 @@ span1 []
                     collect_id: 3,
                     is_root: false,
                     is_sampled: true,
+                    ..Default::default()
                 };
-                let span_line3 = span_stack.register_span_line(Some(token3.into())).unwrap();
+                let span_line3 = span_stack
+                    .register_span_line(Some(token3.clone().into()))
+                    .unwrap();
                 assert_eq!(span_stack.current_collect_token().unwrap().as_slice(), &[
                     token3
                 ]);
@@ -322,8 +368,11 @@ span1 []
                 collect_id: 4,
                 is_root: false,
                 is_sampled: true,
+                ..Default::default()
             };
-            let span_line4 = span_stack.register_span_line(Some(token4.into())).unwrap();
+            let span_line4 = span_stack
+                .register_span_line(Some(token4.clone().into()))
+                .unwrap();
             assert_eq!(span_stack.current_collect_token().unwrap().as_slice(), &[
                 token4
             ]);
@@ -351,6 +400,7 @@ span1 []
                         collect_id: 42,
                         is_root: false,
                         is_sampled: true,
+                        ..Default::default()
                     }
                     .into(),
                 ))
@@ -376,6 +426,7 @@ span1 []
                         collect_id: 42,
                         is_root: false,
                         is_sampled: true,
+                        ..Default::default()
                     }
                     .into(),
                 ))
@@ -401,6 +452,7 @@ span1 []
                         collect_id: 42,
                         is_root: false,
                         is_sampled: true,
+                        ..Default::default()
                     }
                     .into(),
                 ))
@@ -8,6 +8,7 @@ pub(crate) mod local_span_line;
 pub(crate) mod local_span_stack;
 pub(crate) mod raw_span;
 pub(crate) mod span_queue;
+pub(crate) mod thread_info;
 
 pub use self::local_collector::LocalCollector;
 pub use self::local_collector::LocalSpans;
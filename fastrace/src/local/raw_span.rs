@@ -4,7 +4,10 @@ use std::borrow::Cow;
 
 use fastant::Instant;
 
+use crate::collector::LinkRecord;
 use crate::collector::SpanId;
+use crate::collector::SpanKind;
+use crate::collector::Status;
 use crate::util::Properties;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -21,6 +24,11 @@ pub struct RawSpan {
     pub begin_instant: Instant,
     pub name: Cow<'static, str>,
     pub properties: Option<Properties>,
+    /// Boxed to keep `RawSpan` small, as few spans have links.
+    #[allow(clippy::box_collection)]
+    pub links: Option<Box<Vec<LinkRecord>>>,
+    pub status: Status,
+    pub kind: SpanKind,
     pub raw_kind: RawKind,
 
     // Will write this field at post processing
@@ -42,6 +50,9 @@ impl RawSpan {
             begin_instant,
             name: name.into(),
             properties: None,
+            links: None,
+            status: Status::Unset,
+            kind: SpanKind::Internal,
             raw_kind,
             end_instant: Instant::ZERO,
         }
@@ -67,6 +78,9 @@ impl Clone for RawSpan {
             begin_instant: self.begin_instant,
             name: self.name.clone(),
             properties,
+            links: self.links.clone(),
+            status: self.status.clone(),
+            kind: self.kind,
             raw_kind: self.raw_kind,
             end_instant: self.end_instant,
         }
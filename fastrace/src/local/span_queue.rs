@@ -7,6 +7,9 @@ use fastant::Instant;
 use super::raw_span::RawKind;
 use crate::Event;
 use crate::collector::SpanId;
+use crate::collector::SpanKind;
+use crate::collector::Status;
+use crate::collector::Value;
 use crate::local::raw_span::RawSpan;
 use crate::util::Properties;
 use crate::util::RawSpans;
@@ -87,7 +90,7 @@ impl SpanQueue {
     pub fn add_properties<K, V, I>(&mut self, properties: I)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
     {
         if self.span_queue.len() >= self.capacity {
@@ -108,11 +111,29 @@ impl SpanQueue {
         self.span_queue.push(span);
     }
 
+    #[inline]
+    pub fn set_status(&mut self, status: Status) {
+        if self.span_queue.len() >= self.capacity {
+            return;
+        }
+
+        let mut span = RawSpan::begin_with(
+            SpanId::next_id(),
+            self.next_parent_id,
+            Instant::ZERO,
+            Cow::Borrowed(""),
+            RawKind::Properties,
+        );
+        span.status = status;
+
+        self.span_queue.push(span);
+    }
+
     #[inline]
     pub fn with_properties<K, V, I>(&mut self, span_handle: &SpanHandle, properties: I)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
     {
         debug_assert!(span_handle.index < self.span_queue.len());
@@ -123,6 +144,13 @@ impl SpanQueue {
             .extend(properties.into_iter().map(|(k, v)| (k.into(), v.into())));
     }
 
+    #[inline]
+    pub fn with_kind(&mut self, span_handle: &SpanHandle, kind: SpanKind) {
+        debug_assert!(span_handle.index < self.span_queue.len());
+
+        self.span_queue[span_handle.index].kind = kind;
+    }
+
     #[inline]
     pub fn take_queue(self) -> RawSpans {
         self.span_queue
@@ -0,0 +1,70 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static CURRENT_THREAD_INFO: ThreadInfo = ThreadInfo::capture();
+}
+
+/// The id and name of the thread a span ran on.
+///
+/// Thread ids are assigned sequentially by fastrace, starting from 1, the first time a thread
+/// records a span. They are not OS thread ids.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ThreadInfo {
+    pub id: u64,
+    pub name: Option<Arc<str>>,
+}
+
+impl ThreadInfo {
+    /// Returns the info of the current thread, which is captured once per thread.
+    #[inline]
+    pub fn current() -> Self {
+        CURRENT_THREAD_INFO
+            .try_with(Clone::clone)
+            .unwrap_or_else(|_| Self::capture())
+    }
+
+    fn capture() -> Self {
+        ThreadInfo {
+            id: NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed),
+            name: std::thread::current().name().map(Arc::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thread_info() {
+        let current = ThreadInfo::current();
+        assert_eq!(current, ThreadInfo::current());
+
+        let other = std::thread::Builder::new()
+            .name("worker".to_string())
+            .spawn(ThreadInfo::current)
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_ne!(other.id, current.id);
+        assert_eq!(other.name.as_deref(), Some("worker"));
+    }
+}
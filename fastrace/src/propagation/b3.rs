@@ -0,0 +1,207 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::collector::SpanContext;
+use crate::collector::SpanId;
+use crate::collector::TraceContext;
+use crate::collector::TraceId;
+use crate::propagation::Extractor;
+use crate::propagation::Injector;
+use crate::propagation::Propagator;
+use crate::propagation::parse_hex_span_id;
+use crate::propagation::parse_hex_trace_id;
+
+const B3: &str = "b3";
+const TRACE_ID: &str = "x-b3-traceid";
+const SPAN_ID: &str = "x-b3-spanid";
+const SAMPLED: &str = "x-b3-sampled";
+const FLAGS: &str = "x-b3-flags";
+
+/// Propagates the [Zipkin B3](https://github.com/openzipkin/b3-propagation) headers.
+///
+/// Both the single `b3` header and the multiple `X-B3-*` headers are extracted, the single
+/// header being preferred. Only the configured encoding is injected.
+#[derive(Clone, Copy, Debug)]
+pub struct B3Propagator {
+    single_header: bool,
+}
+
+impl B3Propagator {
+    /// Injects the single `b3` header.
+    pub fn single_header() -> Self {
+        Self {
+            single_header: true,
+        }
+    }
+
+    /// Injects the `X-B3-TraceId`, `X-B3-SpanId` and `X-B3-Sampled` headers.
+    pub fn multi_header() -> Self {
+        Self {
+            single_header: false,
+        }
+    }
+}
+
+impl Propagator for B3Propagator {
+    fn inject(&self, context: &TraceContext, injector: &mut dyn Injector) {
+        let context = &context.span_context;
+        let sampled = if context.sampled { "1" } else { "0" };
+        if self.single_header {
+            injector.set(
+                B3,
+                format!("{}-{}-{sampled}", context.trace_id, context.span_id),
+            );
+        } else {
+            injector.set(TRACE_ID, context.trace_id.to_string());
+            injector.set(SPAN_ID, context.span_id.to_string());
+            injector.set(SAMPLED, sampled.to_string());
+        }
+    }
+
+    fn extract(&self, extractor: &dyn Extractor) -> Option<TraceContext> {
+        let context = match extractor.get(B3) {
+            Some(b3) => extract_single_header(b3.trim())?,
+            None => extract_multi_header(extractor)?,
+        };
+        Some(TraceContext::new(context))
+    }
+}
+
+fn extract_single_header(b3: &str) -> Option<SpanContext> {
+    let mut parts = b3.split('-');
+    let trace_id = parse_hex_trace_id(parts.next()?)?;
+    let span_id = parse_hex_span_id(parts.next()?)?;
+    let sampled = match parts.next() {
+        None => true,
+        Some("1" | "d") => true,
+        Some("0") => false,
+        Some(_) => return None,
+    };
+    if let Some(parent_span_id) = parts.next() {
+        parse_hex_span_id(parent_span_id)?;
+    }
+    if parts.next().is_some() || trace_id == 0 || span_id == 0 {
+        return None;
+    }
+
+    Some(SpanContext::new(TraceId(trace_id), SpanId(span_id)).sampled(sampled))
+}
+
+fn extract_multi_header(extractor: &dyn Extractor) -> Option<SpanContext> {
+    let trace_id = parse_hex_trace_id(extractor.get(TRACE_ID)?.trim())?;
+    let span_id = parse_hex_span_id(extractor.get(SPAN_ID)?.trim())?;
+    let debug = extractor.get(FLAGS).map(str::trim) == Some("1");
+    let sampled = match extractor.get(SAMPLED).map(str::trim) {
+        None => true,
+        Some("1" | "true") => true,
+        Some("0" | "false") => debug,
+        Some(_) => return None,
+    };
+    if trace_id == 0 || span_id == 0 {
+        return None;
+    }
+
+    Some(SpanContext::new(TraceId(trace_id), SpanId(span_id)).sampled(sampled))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn headers(headers: &[(&str, &str)]) -> HashMap<String, String> {
+        headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn b3_single_header() {
+        let context = SpanContext::new(TraceId(0x0af7651916cd43dd8448eb211c80319c), SpanId(2));
+        let mut injected = HashMap::new();
+        B3Propagator::single_header().inject(&context.sampled(false).into(), &mut injected);
+        assert_eq!(
+            injected["b3"],
+            "0af7651916cd43dd8448eb211c80319c-0000000000000002-0"
+        );
+
+        let extracted = B3Propagator::single_header().extract(&injected).unwrap();
+        assert_eq!(extracted.span_context.trace_id, context.trace_id);
+        assert_eq!(extracted.span_context.span_id, context.span_id);
+        assert!(!extracted.span_context.sampled);
+
+        let extracted = B3Propagator::multi_header()
+            .extract(&headers(&[(
+                "b3",
+                "463ac35c9f6413ad-a2fb4a1d1a96d312-d-0020000000000001",
+            )]))
+            .unwrap();
+        assert_eq!(extracted.span_context.trace_id, TraceId(0x463ac35c9f6413ad));
+        assert_eq!(extracted.span_context.span_id, SpanId(0xa2fb4a1d1a96d312));
+        assert!(extracted.span_context.sampled);
+
+        for invalid in [
+            "0",
+            "463ac35c9f6413ad",
+            "463ac35c9f6413ad-a2fb4a1d1a96d312-x",
+        ] {
+            assert!(
+                B3Propagator::single_header()
+                    .extract(&headers(&[("b3", invalid)]))
+                    .is_none()
+            );
+        }
+    }
+
+    #[test]
+    fn b3_multi_header() {
+        let context = SpanContext::new(TraceId(1), SpanId(2));
+        let mut injected = HashMap::new();
+        B3Propagator::multi_header().inject(&context.into(), &mut injected);
+        assert_eq!(injected["x-b3-traceid"], "00000000000000000000000000000001");
+        assert_eq!(injected["x-b3-spanid"], "0000000000000002");
+        assert_eq!(injected["x-b3-sampled"], "1");
+
+        let extracted = B3Propagator::single_header().extract(&injected).unwrap();
+        assert_eq!(extracted.span_context.trace_id, context.trace_id);
+        assert_eq!(extracted.span_context.span_id, context.span_id);
+        assert!(extracted.span_context.sampled);
+
+        let extracted = B3Propagator::multi_header()
+            .extract(&headers(&[
+                ("X-B3-TraceId", "463ac35c9f6413ad"),
+                ("X-B3-SpanId", "a2fb4a1d1a96d312"),
+                ("X-B3-Sampled", "0"),
+            ]))
+            .unwrap();
+        assert!(!extracted.span_context.sampled);
+
+        let extracted = B3Propagator::multi_header()
+            .extract(&headers(&[
+                ("X-B3-TraceId", "463ac35c9f6413ad"),
+                ("X-B3-SpanId", "a2fb4a1d1a96d312"),
+                ("X-B3-Flags", "1"),
+            ]))
+            .unwrap();
+        assert!(extracted.span_context.sampled);
+
+        assert!(
+            B3Propagator::multi_header()
+                .extract(&headers(&[("X-B3-TraceId", "463ac35c9f6413ad")]))
+                .is_none()
+        );
+    }
+}
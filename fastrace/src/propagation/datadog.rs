@@ -0,0 +1,131 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::collector::SpanContext;
+use crate::collector::SpanId;
+use crate::collector::TraceContext;
+use crate::collector::TraceId;
+use crate::propagation::Extractor;
+use crate::propagation::Injector;
+use crate::propagation::Propagator;
+use crate::propagation::parse_hex_span_id;
+
+const TRACE_ID: &str = "x-datadog-trace-id";
+const PARENT_ID: &str = "x-datadog-parent-id";
+const SAMPLING_PRIORITY: &str = "x-datadog-sampling-priority";
+const TAGS: &str = "x-datadog-tags";
+const TRACE_ID_HIGH_TAG: &str = "_dd.p.tid";
+
+/// Propagates the [Datadog](https://docs.datadoghq.com/tracing/trace_collection/trace_context_propagation/)
+/// `x-datadog-*` headers.
+///
+/// Datadog trace ids are 64-bit decimal numbers. The upper 64 bits of a 128-bit trace id are
+/// carried in the `_dd.p.tid` tag of the `x-datadog-tags` header.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DatadogPropagator;
+
+impl Propagator for DatadogPropagator {
+    fn inject(&self, context: &TraceContext, injector: &mut dyn Injector) {
+        let context = &context.span_context;
+        injector.set(TRACE_ID, (context.trace_id.0 as u64).to_string());
+        injector.set(PARENT_ID, context.span_id.0.to_string());
+        injector.set(
+            SAMPLING_PRIORITY,
+            if context.sampled { "1" } else { "0" }.to_string(),
+        );
+        let trace_id_high = (context.trace_id.0 >> 64) as u64;
+        if trace_id_high != 0 {
+            injector.set(TAGS, format!("{TRACE_ID_HIGH_TAG}={trace_id_high:016x}"));
+        }
+    }
+
+    fn extract(&self, extractor: &dyn Extractor) -> Option<TraceContext> {
+        let trace_id_low: u64 = extractor.get(TRACE_ID)?.trim().parse().ok()?;
+        let span_id: u64 = extractor.get(PARENT_ID)?.trim().parse().ok()?;
+        let sampled = match extractor.get(SAMPLING_PRIORITY) {
+            Some(priority) => priority.trim().parse::<i32>().ok()? > 0,
+            None => true,
+        };
+        let trace_id_high = extractor
+            .get(TAGS)
+            .and_then(|tags| {
+                tags.split(',')
+                    .filter_map(|tag| tag.split_once('='))
+                    .find(|(key, _)| key.trim() == TRACE_ID_HIGH_TAG)
+                    .and_then(|(_, value)| parse_hex_span_id(value.trim()))
+            })
+            .unwrap_or(0);
+        if trace_id_low == 0 || span_id == 0 {
+            return None;
+        }
+
+        let trace_id = ((trace_id_high as u128) << 64) | trace_id_low as u128;
+        Some(
+            SpanContext::new(TraceId(trace_id), SpanId(span_id))
+                .sampled(sampled)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn datadog_propagator() {
+        let context = SpanContext::new(TraceId(0x640cfd8d00000000_0000000000000003), SpanId(2));
+        let mut headers = HashMap::new();
+        DatadogPropagator.inject(&context.into(), &mut headers);
+        assert_eq!(headers["x-datadog-trace-id"], "3");
+        assert_eq!(headers["x-datadog-parent-id"], "2");
+        assert_eq!(headers["x-datadog-sampling-priority"], "1");
+        assert_eq!(headers["x-datadog-tags"], "_dd.p.tid=640cfd8d00000000");
+
+        let extracted = DatadogPropagator.extract(&headers).unwrap();
+        assert_eq!(extracted.span_context.trace_id, context.trace_id);
+        assert_eq!(extracted.span_context.span_id, context.span_id);
+        assert!(extracted.span_context.sampled);
+
+        let mut headers = HashMap::new();
+        DatadogPropagator.inject(
+            &SpanContext::new(TraceId(3), SpanId(2))
+                .sampled(false)
+                .into(),
+            &mut headers,
+        );
+        assert!(!headers.contains_key("x-datadog-tags"));
+        let extracted = DatadogPropagator.extract(&headers).unwrap();
+        assert_eq!(extracted.span_context.trace_id, TraceId(3));
+        assert!(!extracted.span_context.sampled);
+
+        let headers = HashMap::from([
+            ("x-datadog-trace-id".to_string(), "7".to_string()),
+            ("x-datadog-parent-id".to_string(), "9".to_string()),
+            ("x-datadog-sampling-priority".to_string(), "2".to_string()),
+            (
+                "x-datadog-tags".to_string(),
+                "_dd.p.dm=-4,_dd.p.tid=0000000000000001".to_string(),
+            ),
+        ]);
+        let extracted = DatadogPropagator.extract(&headers).unwrap();
+        assert_eq!(extracted.span_context.trace_id, TraceId((1 << 64) | 7));
+        assert!(extracted.span_context.sampled);
+
+        let headers = HashMap::from([("x-datadog-trace-id".to_string(), "7".to_string())]);
+        assert!(DatadogPropagator.extract(&headers).is_none());
+    }
+}
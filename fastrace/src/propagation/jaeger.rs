@@ -0,0 +1,141 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::collector::Baggage;
+use crate::collector::SpanContext;
+use crate::collector::SpanId;
+use crate::collector::TraceContext;
+use crate::collector::TraceId;
+use crate::propagation::Extractor;
+use crate::propagation::Injector;
+use crate::propagation::Propagator;
+use crate::propagation::parse_hex_u128;
+
+const UBER_TRACE_ID: &str = "uber-trace-id";
+const BAGGAGE_PREFIX: &str = "uberctx-";
+
+/// Propagates the [Jaeger](https://www.jaegertracing.io/docs/latest/client-libraries/#propagation-format)
+/// `uber-trace-id` header, and the baggage in `uberctx-{key}` headers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JaegerPropagator;
+
+impl Propagator for JaegerPropagator {
+    fn inject(&self, context: &TraceContext, injector: &mut dyn Injector) {
+        let span_context = &context.span_context;
+        injector.set(
+            UBER_TRACE_ID,
+            format!(
+                "{}:{}:0:{}",
+                span_context.trace_id, span_context.span_id, span_context.sampled as u8
+            ),
+        );
+        for (key, value) in context.baggage.iter() {
+            injector.set(&format!("{BAGGAGE_PREFIX}{key}"), value.to_string());
+        }
+    }
+
+    fn extract(&self, extractor: &dyn Extractor) -> Option<TraceContext> {
+        let header = extractor.get(UBER_TRACE_ID)?.trim();
+        let header = if header.contains(':') {
+            header.to_string()
+        } else {
+            header.replace("%3A", ":").replace("%3a", ":")
+        };
+
+        let mut parts = header.split(':');
+        let trace_id = parse_jaeger_id(parts.next()?, 32)?;
+        let span_id = parse_jaeger_id(parts.next()?, 16)? as u64;
+        let _parent_span_id = parse_jaeger_id(parts.next()?, 16)?;
+        let flags = parse_jaeger_id(parts.next()?, 2)?;
+        if parts.next().is_some() || trace_id == 0 || span_id == 0 {
+            return None;
+        }
+
+        let baggage = extractor
+            .keys()
+            .into_iter()
+            .filter_map(|key| {
+                let name = key
+                    .get(..BAGGAGE_PREFIX.len())
+                    .filter(|prefix| prefix.eq_ignore_ascii_case(BAGGAGE_PREFIX))
+                    .map(|_| &key[BAGGAGE_PREFIX.len()..])?;
+                Some((name.to_ascii_lowercase(), extractor.get(key)?.to_string()))
+            })
+            .fold(Baggage::default(), |baggage, (k, v)| baggage.insert(k, v));
+
+        let span_context =
+            SpanContext::new(TraceId(trace_id), SpanId(span_id)).sampled(flags & 1 == 1);
+        Some(TraceContext::new(span_context).with_baggage(baggage))
+    }
+}
+
+/// Parses an id of at most `max_len` hex digits, whose leading zeros may be omitted.
+fn parse_jaeger_id(s: &str, max_len: usize) -> Option<u128> {
+    if s.len() > max_len {
+        return None;
+    }
+    parse_hex_u128(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn jaeger_propagator() {
+        let context = TraceContext::new(SpanContext::new(
+            TraceId(0x0af7651916cd43dd8448eb211c80319c),
+            SpanId(2),
+        ))
+        .with_baggage(Baggage::new([("user", "alice")]));
+        let mut headers = HashMap::new();
+        JaegerPropagator.inject(&context, &mut headers);
+        assert_eq!(
+            headers["uber-trace-id"],
+            "0af7651916cd43dd8448eb211c80319c:0000000000000002:0:1"
+        );
+        assert_eq!(headers["uberctx-user"], "alice");
+
+        let extracted = JaegerPropagator.extract(&headers).unwrap();
+        assert_eq!(
+            extracted.span_context.trace_id,
+            context.span_context.trace_id
+        );
+        assert_eq!(extracted.span_context.span_id, context.span_context.span_id);
+        assert!(extracted.span_context.sampled);
+        assert_eq!(extracted.baggage.get("user"), Some("alice"));
+
+        let mut headers = HashMap::new();
+        headers.insert("Uber-Trace-Id".to_string(), "abc%3A1f%3A0%3A0".to_string());
+        headers.insert("Uberctx-Tenant".to_string(), "acme".to_string());
+        let extracted = JaegerPropagator.extract(&headers).unwrap();
+        assert_eq!(extracted.span_context.trace_id, TraceId(0xabc));
+        assert_eq!(extracted.span_context.span_id, SpanId(0x1f));
+        assert!(!extracted.span_context.sampled);
+        assert_eq!(extracted.baggage.get("tenant"), Some("acme"));
+
+        for invalid in [
+            "abc:1f:0",
+            "0:1f:0:1",
+            "abc:0:0:1",
+            "abc:1f:0:1:2",
+            "x:1f:0:1",
+        ] {
+            let headers = HashMap::from([("uber-trace-id".to_string(), invalid.to_string())]);
+            assert!(JaegerPropagator.extract(&headers).is_none());
+        }
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Propagation of [`TraceContext`] across process boundaries through headers.
+//!
+//! A [`Propagator`] writes a `TraceContext` into outgoing headers with
+//! [`inject()`](Propagator::inject), and reads it back from incoming headers with
+//! [`extract()`](Propagator::extract). Headers are accessed through the [`Injector`] and
+//! [`Extractor`] traits, which are implemented for `HashMap<String, String>`, and for
+//! `http::HeaderMap` when the `http` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use fastrace::collector::TraceContext;
+//! use fastrace::prelude::*;
+//! use fastrace::propagation::B3Propagator;
+//! use fastrace::propagation::CompositePropagator;
+//! use fastrace::propagation::Propagator;
+//! use fastrace::propagation::W3CPropagator;
+//!
+//! let propagator = CompositePropagator::new()
+//!     .with(W3CPropagator)
+//!     .with(B3Propagator::multi_header());
+//!
+//! let mut headers = HashMap::new();
+//! headers.insert(
+//!     "X-B3-TraceId".to_string(),
+//!     "0af7651916cd43dd8448eb211c80319c".to_string(),
+//! );
+//! headers.insert("X-B3-SpanId".to_string(), "b7ad6b7169203331".to_string());
+//!
+//! let parent = propagator.extract(&headers).unwrap();
+//! let root = Span::root_with_context("root", parent);
+//!
+//! let mut outgoing = HashMap::new();
+//! propagator.inject(&TraceContext::from_span(&root).unwrap(), &mut outgoing);
+//! assert!(outgoing.contains_key("traceparent"));
+//! assert!(outgoing.contains_key("x-b3-traceid"));
+//! ```
+//!
+//! [`TraceContext`]: crate::collector::TraceContext
+
+mod b3;
+mod datadog;
+mod jaeger;
+mod w3c;
+
+use std::collections::HashMap;
+
+pub use b3::B3Propagator;
+pub use datadog::DatadogPropagator;
+pub use jaeger::JaegerPropagator;
+pub use w3c::W3CPropagator;
+
+use crate::collector::TraceContext;
+
+/// Writes headers into a carrier.
+pub trait Injector {
+    /// Sets the header `key` to `value`, replacing the previous value if any.
+    fn set(&mut self, key: &str, value: String);
+}
+
+/// Reads headers from a carrier.
+pub trait Extractor {
+    /// Returns the value of the header `key`, which is matched case-insensitively.
+    fn get(&self, key: &str) -> Option<&str>;
+
+    /// Returns the names of all the headers.
+    fn keys(&self) -> Vec<&str>;
+}
+
+/// Keys are stored in lowercase, as HTTP header names are case-insensitive.
+impl<S: std::hash::BuildHasher> Injector for HashMap<String, String, S> {
+    fn set(&mut self, key: &str, value: String) {
+        self.insert(key.to_ascii_lowercase(), value);
+    }
+}
+
+impl<S: std::hash::BuildHasher> Extractor for HashMap<String, String, S> {
+    fn get(&self, key: &str) -> Option<&str> {
+        match HashMap::get(self, key) {
+            Some(value) => Some(value),
+            None => self
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v.as_str()),
+        }
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        HashMap::keys(self).map(String::as_str).collect()
+    }
+}
+
+/// Values that are not valid header values are skipped.
+#[cfg(feature = "http")]
+impl Injector for http::HeaderMap {
+    fn set(&mut self, key: &str, value: String) {
+        let Ok(name) = http::header::HeaderName::try_from(key) else {
+            return;
+        };
+        let Ok(value) = http::header::HeaderValue::try_from(value) else {
+            return;
+        };
+        self.insert(name, value);
+    }
+}
+
+/// Values that are not visible ASCII are ignored.
+#[cfg(feature = "http")]
+impl Extractor for http::HeaderMap {
+    fn get(&self, key: &str) -> Option<&str> {
+        http::HeaderMap::get(self, key)?.to_str().ok()
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        http::HeaderMap::keys(self)
+            .map(http::header::HeaderName::as_str)
+            .collect()
+    }
+}
+
+/// A header format for propagating a [`TraceContext`] across process boundaries.
+///
+/// [`TraceContext`]: crate::collector::TraceContext
+pub trait Propagator: Send + Sync + 'static {
+    /// Writes `context` into the headers of `injector`.
+    fn inject(&self, context: &TraceContext, injector: &mut dyn Injector);
+
+    /// Reads a `TraceContext` from the headers of `extractor`, or returns `None` if the headers
+    /// are missing or malformed.
+    fn extract(&self, extractor: &dyn Extractor) -> Option<TraceContext>;
+}
+
+/// A propagator that injects with all of its propagators and extracts with the first one that
+/// succeeds.
+///
+/// # Examples
+///
+/// ```
+/// use fastrace::propagation::B3Propagator;
+/// use fastrace::propagation::CompositePropagator;
+/// use fastrace::propagation::DatadogPropagator;
+/// use fastrace::propagation::JaegerPropagator;
+/// use fastrace::propagation::W3CPropagator;
+///
+/// let propagator = CompositePropagator::new()
+///     .with(W3CPropagator)
+///     .with(B3Propagator::single_header())
+///     .with(JaegerPropagator)
+///     .with(DatadogPropagator);
+/// ```
+#[derive(Default)]
+pub struct CompositePropagator {
+    propagators: Vec<Box<dyn Propagator>>,
+}
+
+impl CompositePropagator {
+    /// Creates an empty `CompositePropagator`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a propagator, tried after the ones already added.
+    pub fn with(mut self, propagator: impl Propagator) -> Self {
+        self.propagators.push(Box::new(propagator));
+        self
+    }
+}
+
+impl Propagator for CompositePropagator {
+    fn inject(&self, context: &TraceContext, injector: &mut dyn Injector) {
+        for propagator in &self.propagators {
+            propagator.inject(context, injector);
+        }
+    }
+
+    fn extract(&self, extractor: &dyn Extractor) -> Option<TraceContext> {
+        self.propagators
+            .iter()
+            .find_map(|propagator| propagator.extract(extractor))
+    }
+}
+
+/// Parses a trace id of 16 or 32 hex digits.
+fn parse_hex_trace_id(s: &str) -> Option<u128> {
+    if s.len() != 16 && s.len() != 32 {
+        return None;
+    }
+    parse_hex_u128(s)
+}
+
+/// Parses a span id of 16 hex digits.
+fn parse_hex_span_id(s: &str) -> Option<u64> {
+    if s.len() != 16 {
+        return None;
+    }
+    parse_hex_u128(s).map(|id| id as u64)
+}
+
+fn parse_hex_u128(s: &str) -> Option<u128> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u128::from_str_radix(s, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::SpanContext;
+    use crate::collector::SpanId;
+    use crate::collector::TraceId;
+
+    #[test]
+    fn hash_map_carrier() {
+        let mut headers = HashMap::new();
+        Injector::set(&mut headers, "X-Header", "value".to_string());
+        assert_eq!(headers.get("x-header").map(String::as_str), Some("value"));
+        assert_eq!(Extractor::get(&headers, "X-HEADER"), Some("value"));
+
+        let mut headers = HashMap::new();
+        headers.insert("Mixed-Case".to_string(), "value".to_string());
+        assert_eq!(Extractor::get(&headers, "mixed-case"), Some("value"));
+        assert_eq!(Extractor::keys(&headers), vec!["Mixed-Case"]);
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn header_map_carrier() {
+        let mut headers = http::HeaderMap::new();
+        W3CPropagator.inject(
+            &SpanContext::new(TraceId(1), SpanId(2)).into(),
+            &mut headers,
+        );
+        assert_eq!(
+            headers["traceparent"],
+            "00-00000000000000000000000000000001-0000000000000002-01"
+        );
+        assert_eq!(Extractor::keys(&headers), vec!["traceparent"]);
+
+        let extracted = W3CPropagator.extract(&headers).unwrap();
+        assert_eq!(extracted.span_context.trace_id, TraceId(1));
+        assert_eq!(extracted.span_context.span_id, SpanId(2));
+
+        Injector::set(&mut headers, "invalid", "\n".to_string());
+        assert!(Extractor::get(&headers, "invalid").is_none());
+    }
+
+    #[test]
+    fn composite_propagator() {
+        let propagator = CompositePropagator::new()
+            .with(W3CPropagator)
+            .with(B3Propagator::multi_header());
+        let context = SpanContext::new(TraceId(1), SpanId(2));
+
+        let mut headers = HashMap::new();
+        propagator.inject(&context.into(), &mut headers);
+        assert!(headers.contains_key("traceparent"));
+        assert!(headers.contains_key("x-b3-traceid"));
+
+        headers.remove("traceparent");
+        let extracted = propagator.extract(&headers).unwrap();
+        assert_eq!(extracted.span_context.trace_id, TraceId(1));
+        assert_eq!(extracted.span_context.span_id, SpanId(2));
+
+        assert!(propagator.extract(&HashMap::new()).is_none());
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::collector::Baggage;
+use crate::collector::SpanContext;
+use crate::collector::TraceContext;
+use crate::collector::TraceState;
+use crate::propagation::Extractor;
+use crate::propagation::Injector;
+use crate::propagation::Propagator;
+
+const TRACEPARENT: &str = "traceparent";
+const TRACESTATE: &str = "tracestate";
+const BAGGAGE: &str = "baggage";
+
+/// Propagates the [W3C Trace Context](https://www.w3.org/TR/trace-context/) `traceparent` and
+/// `tracestate` headers, and the [W3C Baggage](https://www.w3.org/TR/baggage/) `baggage` header.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct W3CPropagator;
+
+impl Propagator for W3CPropagator {
+    fn inject(&self, context: &TraceContext, injector: &mut dyn Injector) {
+        injector.set(TRACEPARENT, context.span_context.encode_w3c_traceparent());
+        if !context.trace_state.is_empty() {
+            injector.set(TRACESTATE, context.trace_state.encode_w3c_tracestate());
+        }
+        if !context.baggage.is_empty() {
+            injector.set(BAGGAGE, context.baggage.encode_w3c_baggage());
+        }
+    }
+
+    fn extract(&self, extractor: &dyn Extractor) -> Option<TraceContext> {
+        let context = SpanContext::decode_w3c_traceparent(extractor.get(TRACEPARENT)?.trim())?;
+        let trace_state = extractor
+            .get(TRACESTATE)
+            .map(TraceState::decode_w3c_tracestate)
+            .unwrap_or_default();
+        let baggage = extractor
+            .get(BAGGAGE)
+            .map(Baggage::decode_w3c_baggage)
+            .unwrap_or_default();
+        Some(
+            TraceContext::new(context)
+                .with_trace_state(trace_state)
+                .with_baggage(baggage),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::collector::SpanId;
+    use crate::collector::TraceId;
+
+    #[test]
+    fn w3c_propagator() {
+        let context = TraceContext::new(SpanContext::new(
+            TraceId(0x0af7651916cd43dd8448eb211c80319c),
+            SpanId(1),
+        ))
+        .with_trace_state(TraceState::new([("congo", "t61rcWkgMzE")]))
+        .with_baggage(Baggage::new([("user.id", "alice")]));
+
+        let mut headers = HashMap::new();
+        W3CPropagator.inject(&context, &mut headers);
+        assert_eq!(
+            headers["traceparent"],
+            "00-0af7651916cd43dd8448eb211c80319c-0000000000000001-01"
+        );
+        assert_eq!(headers["tracestate"], "congo=t61rcWkgMzE");
+        assert_eq!(headers["baggage"], "user.id=alice");
+
+        let extracted = W3CPropagator.extract(&headers).unwrap();
+        assert_eq!(
+            extracted.span_context.trace_id,
+            context.span_context.trace_id
+        );
+        assert_eq!(extracted.span_context.span_id, context.span_context.span_id);
+        assert!(extracted.span_context.sampled);
+        assert_eq!(extracted.trace_state, context.trace_state);
+        assert_eq!(extracted.baggage, context.baggage);
+
+        let mut headers = HashMap::new();
+        W3CPropagator.inject(
+            &SpanContext::new(TraceId(1), SpanId(2)).into(),
+            &mut headers,
+        );
+        assert_eq!(headers.len(), 1);
+
+        headers.insert("traceparent".to_string(), "invalid".to_string());
+        assert!(W3CPropagator.extract(&headers).is_none());
+    }
+}
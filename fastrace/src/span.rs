@@ -12,10 +12,16 @@ use fastant::Instant;
 use crate::Event;
 use crate::collector::CollectTokenItem;
 use crate::collector::GlobalCollect;
+use crate::collector::LinkRecord;
 use crate::collector::SpanContext;
 use crate::collector::SpanId;
+use crate::collector::SpanKind;
 use crate::collector::SpanSet;
+use crate::collector::Status;
+use crate::collector::TraceContext;
+use crate::collector::Value;
 use crate::collector::global_collector::NOT_SAMPLED_COLLECT_ID;
+use crate::collector::sampler;
 use crate::local::LocalCollector;
 use crate::local::LocalSpans;
 use crate::local::local_collector::LocalSpansInner;
@@ -23,6 +29,7 @@ use crate::local::local_span_stack::LOCAL_SPAN_STACK;
 use crate::local::local_span_stack::LocalSpanStack;
 use crate::local::raw_span::RawKind;
 use crate::local::raw_span::RawSpan;
+use crate::local::thread_info::ThreadInfo;
 use crate::util::CollectToken;
 use crate::util::Properties;
 
@@ -71,6 +78,10 @@ impl Span {
     /// Once dropped, the root span automatically submits all associated child spans to the
     /// reporter.
     ///
+    /// Whether the trace is sampled is decided by the sampler set by
+    /// [`set_sampler()`](crate::set_sampler), or by the `sampled` flag of `parent` if no sampler
+    /// is set.
+    ///
     /// # Examples
     ///
     /// ```
@@ -80,6 +91,31 @@ impl Span {
     /// ```
     #[inline]
     pub fn root(name: impl Into<Cow<'static, str>>, parent: SpanContext) -> Self {
+        Self::root_with_context(name, TraceContext::new(parent))
+    }
+
+    /// Create a new trace and return its root span, like [`Span::root()`].
+    ///
+    /// The [`TraceState`] and [`Baggage`] of `parent` are inherited by all the spans of the
+    /// trace, and can be read back from [`TraceContext::from_span()`] or
+    /// [`TraceContext::current_local_parent()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::TraceContext;
+    /// use fastrace::collector::TraceState;
+    /// use fastrace::prelude::*;
+    ///
+    /// let parent = TraceContext::new(SpanContext::random())
+    ///     .with_trace_state(TraceState::decode_w3c_tracestate("congo=t61rcWkgMzE"));
+    /// let root = Span::root_with_context("root", parent);
+    /// ```
+    ///
+    /// [`TraceState`]: crate::collector::TraceState
+    /// [`Baggage`]: crate::collector::Baggage
+    #[inline]
+    pub fn root_with_context(name: impl Into<Cow<'static, str>>, parent: TraceContext) -> Self {
         #[cfg(not(feature = "enable"))]
         {
             Self::noop()
@@ -87,7 +123,14 @@ impl Span {
 
         #[cfg(feature = "enable")]
         {
-            let collect_id = if parent.sampled {
+            let TraceContext {
+                span_context: parent,
+                trace_state,
+                baggage,
+            } = parent;
+            let name = name.into();
+            let decision = sampler::should_sample(&name, &parent);
+            let collect_id = if decision.sampled {
                 current_collect().start_collect()
             } else {
                 NOT_SAMPLED_COLLECT_ID
@@ -98,11 +141,19 @@ impl Span {
                 parent_id: parent.span_id,
                 collect_id,
                 is_root: true,
-                is_sampled: parent.sampled,
+                is_sampled: decision.sampled,
+                trace_state,
+                baggage,
             }
             .into();
 
-            Self::new(token, name, Some(collect_id))
+            let mut span = Self::new(token, name, Some(collect_id));
+            if decision.sampled && !decision.properties.is_empty() {
+                if let Some(inner) = span.inner.as_mut() {
+                    inner.add_properties(|| decision.properties);
+                }
+            }
+            span
         }
     }
 
@@ -253,7 +304,7 @@ impl Span {
     pub fn with_property<K, V, F>(self, property: F) -> Self
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         F: FnOnce() -> (K, V),
     {
         self.with_properties(|| [property()])
@@ -273,7 +324,7 @@ impl Span {
     pub fn with_properties<K, V, I, F>(mut self, properties: F) -> Self
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -284,6 +335,27 @@ impl Span {
         self
     }
 
+    /// Sets the kind of the `Span` and returns the modified `Span`.
+    ///
+    /// Spans are [`SpanKind::Internal`] by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::SpanKind;
+    /// use fastrace::prelude::*;
+    ///
+    /// let root = Span::root("GET /users", SpanContext::random()).with_kind(SpanKind::Server);
+    /// ```
+    #[inline]
+    pub fn with_kind(mut self, kind: SpanKind) -> Self {
+        #[cfg(feature = "enable")]
+        if let Some(inner) = self.inner.as_mut() {
+            inner.raw_span.kind = kind;
+        }
+        self
+    }
+
     /// Add a single property to the `Span`.
     ///
     /// A property is an arbitrary key-value pair associated with a span.
@@ -301,7 +373,7 @@ impl Span {
     pub fn add_property<K, V, F>(&self, property: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         F: FnOnce() -> (K, V),
     {
         self.add_properties(move || [property()])
@@ -322,7 +394,7 @@ impl Span {
     pub fn add_properties<K, V, I, F>(&self, properties: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -336,6 +408,71 @@ impl Span {
         }
     }
 
+    /// Add a link to another span and return the modified `Span`.
+    ///
+    /// A link records a causal relationship that is not parent-child, for example, a span
+    /// processing a batch of messages can link to the span that produced each message. The
+    /// linked span may belong to a different trace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::prelude::*;
+    ///
+    /// let producer = SpanContext::random();
+    ///
+    /// let root = Span::root("consume", SpanContext::random())
+    ///     .with_link(producer, || [("messaging.message.id", "42")]);
+    /// ```
+    #[inline]
+    pub fn with_link<K, V, I, F>(mut self, context: SpanContext, properties: F) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Value>,
+        I: IntoIterator<Item = (K, V)>,
+        F: FnOnce() -> I,
+    {
+        #[cfg(feature = "enable")]
+        if let Some(inner) = self.inner.as_mut() {
+            inner.add_link(context, properties);
+        }
+        self
+    }
+
+    /// Add a link to another span.
+    ///
+    /// See [`Span::with_link()`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::prelude::*;
+    ///
+    /// let root = Span::root("consume", SpanContext::random());
+    ///
+    /// for message in 0..3 {
+    ///     let producer = SpanContext::random();
+    ///     root.add_link(producer, || [("messaging.message.id", message.to_string())]);
+    /// }
+    /// ```
+    #[inline]
+    pub fn add_link<K, V, I, F>(&self, context: SpanContext, properties: F)
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Value>,
+        I: IntoIterator<Item = (K, V)>,
+        F: FnOnce() -> I,
+    {
+        #[cfg(feature = "enable")]
+        {
+            let mut span = Span::enter_with_parent("", self).with_link(context, properties);
+            if let Some(mut inner) = span.inner.take() {
+                inner.raw_span.raw_kind = RawKind::Properties;
+                inner.submit_spans();
+            }
+        }
+    }
+
     /// Adds an event to the `Span`.
     ///
     /// # Examples
@@ -360,6 +497,56 @@ impl Span {
         }
     }
 
+    /// Sets the status of the `Span`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::collector::Status;
+    /// use fastrace::prelude::*;
+    ///
+    /// let root = Span::root("root", SpanContext::random());
+    ///
+    /// root.set_status(Status::Error("connection refused".into()));
+    /// ```
+    #[inline]
+    pub fn set_status(&self, status: Status) {
+        #[cfg(feature = "enable")]
+        {
+            let mut span = Span::enter_with_parent("", self);
+            if let Some(mut inner) = span.inner.take() {
+                inner.raw_span.raw_kind = RawKind::Properties;
+                inner.raw_span.status = status;
+                inner.submit_spans();
+            }
+        }
+    }
+
+    /// Records an error on the `Span`.
+    ///
+    /// The status of the span is set to [`Status::Error`], and an `exception` event is added
+    /// with the message of the error and of each of its sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fastrace::prelude::*;
+    ///
+    /// let root = Span::root("root", SpanContext::random());
+    ///
+    /// if let Err(err) = std::fs::read("/nonexistent") {
+    ///     root.record_error(&err);
+    /// }
+    /// ```
+    #[inline]
+    pub fn record_error(&self, error: &dyn std::error::Error) {
+        #[cfg(feature = "enable")]
+        if self.inner.is_some() {
+            self.add_event(Event::from_error(error));
+            self.set_status(Status::Error(error.to_string().into()));
+        }
+    }
+
     /// Attach a collection of [`LocalSpan`] instances as child spans to the current span.
     ///
     /// This method allows you to associate previously collected `LocalSpan` instances with the
@@ -512,7 +699,7 @@ impl SpanInner {
     fn add_properties<K, V, I, F>(&mut self, properties: F)
     where
         K: Into<Cow<'static, str>>,
-        V: Into<Cow<'static, str>>,
+        V: Into<Value>,
         I: IntoIterator<Item = (K, V)>,
         F: FnOnce() -> I,
     {
@@ -522,6 +709,28 @@ impl SpanInner {
             .extend(properties().into_iter().map(|(k, v)| (k.into(), v.into())));
     }
 
+    #[inline]
+    fn add_link<K, V, I, F>(&mut self, context: SpanContext, properties: F)
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Value>,
+        I: IntoIterator<Item = (K, V)>,
+        F: FnOnce() -> I,
+    {
+        self.raw_span
+            .links
+            .get_or_insert_with(Box::default)
+            .push(LinkRecord {
+                trace_id: context.trace_id,
+                span_id: context.span_id,
+                sampled: context.sampled,
+                properties: properties()
+                    .into_iter()
+                    .map(|(k, v)| (k.into(), v.into()))
+                    .collect(),
+            });
+    }
+
     #[inline]
     fn capture_local_spans(&self, stack: Rc<RefCell<LocalSpanStack>>) -> LocalParentGuard {
         let token = self.issue_collect_token().collect();
@@ -552,13 +761,17 @@ impl SpanInner {
                 collect_id: collect_item.collect_id,
                 is_root: false,
                 is_sampled: collect_item.is_sampled,
+                trace_state: collect_item.trace_state.clone(),
+                baggage: collect_item.baggage.clone(),
             })
     }
 
     #[inline]
     pub(crate) fn submit_spans(self) {
-        self.collect
-            .submit_spans(SpanSet::Span(self.raw_span), self.collect_token);
+        self.collect.submit_spans(
+            SpanSet::Span(self.raw_span, ThreadInfo::current()),
+            self.collect_token,
+        );
     }
 }
 
@@ -701,6 +914,7 @@ mod tests {
                         collect_id: 42,
                         is_root: true,
                         is_sampled: true,
+                        ..Default::default()
                     }
                     .into(),
                 ),
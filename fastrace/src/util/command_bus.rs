@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use parking_lot::Mutex;
@@ -19,7 +22,7 @@ use parking_lot::Mutex;
 use crate::util::spsc;
 
 pub(crate) struct CommandBus<T> {
-    rxs: Mutex<Vec<spsc::Receiver<T>>>,
+    rxs: Mutex<Vec<(spsc::Receiver<T>, Arc<AtomicUsize>)>>,
     notify: Notify,
 }
 
@@ -33,15 +36,18 @@ impl<T> CommandBus<T> {
 
     pub fn sender(&self, capacity: usize) -> CommandSender<T> {
         let (tx, rx) = spsc::bounded(capacity);
-        self.rxs.lock().push(rx);
+        let pending = Arc::new(AtomicUsize::new(0));
+        self.rxs.lock().push((rx, pending.clone()));
         CommandSender {
             tx,
             notify: self.notify.sender(),
+            pending,
+            published_pending: 0,
         }
     }
 
     pub fn drain(&self, mut f: impl FnMut(T)) {
-        self.rxs.lock().retain_mut(|rx| {
+        self.rxs.lock().retain_mut(|(rx, _)| {
             loop {
                 match rx.try_recv() {
                     Ok(Some(msg)) => f(msg),
@@ -52,24 +58,72 @@ impl<T> CommandBus<T> {
         });
     }
 
+    /// Returns the largest number of messages a sender is waiting to send, as last published by
+    /// the senders.
+    pub fn max_pending_len(&self) -> usize {
+        self.rxs
+            .lock()
+            .iter()
+            .map(|(_, pending)| pending.load(Ordering::Relaxed))
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn wait_timeout(&self, timeout: Duration) {
         self.notify.wait_timeout(timeout);
     }
+
+    /// Wakes up the thread blocked in [`CommandBus::wait_timeout`].
+    pub fn notify(&self) {
+        #[cfg(not(target_family = "wasm"))]
+        self.notify.notify_tx.try_send(()).ok();
+    }
 }
 
 pub(crate) struct CommandSender<T> {
     tx: spsc::Sender<T>,
     notify: NotifySender,
+    /// The number of pending messages, read by [`CommandBus::max_pending_len`].
+    pending: Arc<AtomicUsize>,
+    /// The last value stored in `pending`, so that it is only written when it changes.
+    published_pending: usize,
 }
 
 impl<T> CommandSender<T> {
     pub fn send(&mut self, value: T) {
         self.tx.send(value);
+        self.publish_pending();
         #[cfg(not(target_family = "wasm"))]
         if self.tx.is_under_pressure() {
             self.notify.notify();
         }
     }
+
+    #[inline]
+    pub fn flush_pending(&mut self) {
+        self.tx.flush_pending();
+        self.publish_pending();
+    }
+
+    #[inline]
+    pub fn pending_len(&self) -> usize {
+        self.tx.pending_len()
+    }
+
+    pub fn remove_pending(&mut self, f: impl FnMut(&T) -> bool) -> Option<T> {
+        let removed = self.tx.remove_pending(f);
+        self.publish_pending();
+        removed
+    }
+
+    #[inline]
+    fn publish_pending(&mut self) {
+        let pending = self.tx.pending_len();
+        if pending != self.published_pending {
+            self.published_pending = pending;
+            self.pending.store(pending, Ordering::Relaxed);
+        }
+    }
 }
 
 struct Notify {
@@ -8,14 +8,23 @@ pub mod tree;
 use std::borrow::Cow;
 
 use crate::collector::CollectTokenItem;
+use crate::collector::Value;
 use crate::local::raw_span::RawSpan;
 
 pub type RawSpans = Vec<RawSpan>;
 pub type CollectToken = Vec<CollectTokenItem>;
-pub type Properties = Vec<(Cow<'static, str>, Cow<'static, str>)>;
+pub type Properties = Vec<(Cow<'static, str>, Value)>;
 
 impl From<CollectTokenItem> for CollectToken {
     fn from(item: CollectTokenItem) -> Self {
         vec![item]
     }
 }
+
+/// Calls `f`. Used by [`#[trace]`](crate::trace) to run a function body in a closure, which is
+/// inferred to be `FnOnce` when passed here, so that it may return references to the variables
+/// it captures.
+#[inline(always)]
+pub fn call_once<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
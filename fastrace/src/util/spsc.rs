@@ -49,17 +49,37 @@ impl<T> Sender<T> {
     }
 
     pub fn send(&mut self, value: T) {
+        self.flush_pending();
+        if !self.pending_msgs.is_empty() {
+            self.pending_msgs.push_back(value);
+            return;
+        }
+
+        if let Err(PushError::Full(value)) = self.tx.push(value) {
+            self.pending_msgs.push_back(value);
+        }
+    }
+
+    /// Moves as many pending messages as possible into the channel.
+    pub fn flush_pending(&mut self) {
         while let Some(pending_value) = self.pending_msgs.pop_front() {
             if let Err(PushError::Full(pending_value)) = self.tx.push(pending_value) {
                 self.pending_msgs.push_front(pending_value);
-                self.pending_msgs.push_back(value);
                 return;
             }
         }
+    }
 
-        if let Err(PushError::Full(value)) = self.tx.push(value) {
-            self.pending_msgs.push_back(value);
-        }
+    /// Returns the number of messages that are waiting for free slots in the channel.
+    #[inline]
+    pub fn pending_len(&self) -> usize {
+        self.pending_msgs.len()
+    }
+
+    /// Removes and returns the oldest pending message that matches the predicate.
+    pub fn remove_pending(&mut self, f: impl FnMut(&T) -> bool) -> Option<T> {
+        let index = self.pending_msgs.iter().position(f)?;
+        self.pending_msgs.remove(index)
     }
 }
 
@@ -124,7 +124,7 @@ impl Tree {
                     .or_default()
                     .insert(Some(SpanId(0)), ("".into(), vec![], vec![], vec![]));
                 match span_set {
-                    SpanSet::Span(span) => {
+                    SpanSet::Span(span, _) => {
                         collect.entry(item.collect_id).or_default().insert(
                             Some(span.id),
                             (
@@ -192,7 +192,7 @@ impl Tree {
         for (span_set, token) in span_sets {
             for item in token.iter() {
                 match span_set {
-                    SpanSet::Span(span) => {
+                    SpanSet::Span(span, _) => {
                         let parent_id = span.parent_id.unwrap_or(item.parent_id);
                         collect
                             .get_mut(&item.collect_id)
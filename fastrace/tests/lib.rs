@@ -1,13 +1,32 @@
 // Copyright 2020 TiKV Project Authors. Licensed under Apache-2.0.
 
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
+use fastrace::Filter;
+use fastrace::Level;
+use fastrace::collector::AlwaysSample;
+use fastrace::collector::Baggage;
 use fastrace::collector::Config;
 use fastrace::collector::ConsoleReporter;
+use fastrace::collector::LinkRecord;
+use fastrace::collector::NeverSample;
+use fastrace::collector::ParentBasedSampler;
+use fastrace::collector::PerNameSampler;
+use fastrace::collector::RateLimitingSampler;
+use fastrace::collector::Reporter;
+use fastrace::collector::SpanKind;
+use fastrace::collector::Status;
 use fastrace::collector::TestReporter;
+use fastrace::collector::TraceContext;
+use fastrace::collector::TraceState;
+use fastrace::collector::Value;
 use fastrace::local::LocalCollector;
 use fastrace::prelude::*;
 use fastrace::util::tree::tree_str_from_span_records;
+use parking_lot::Mutex;
 use serial_test::serial;
 use tokio::runtime::Builder;
 
@@ -616,6 +635,49 @@ fn test_property() {
     "###);
 }
 
+#[test]
+#[serial]
+fn test_typed_property() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random())
+            .with_property(|| ("str", "v"))
+            .with_property(|| ("i64", -1))
+            .with_property(|| ("f64", 0.5))
+            .with_property(|| ("bool", true));
+        let _g = root.set_local_parent();
+        let _span = LocalSpan::enter_with_local_parent("span")
+            .with_property(|| ("bytes", vec![0u8, 1]))
+            .with_property(|| ("array", vec![Value::from(1), Value::from(2)]));
+        LocalSpan::add_event(Event::new("event").with_property(|| ("u32", 42u32)));
+    }
+
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    let root = spans.iter().find(|span| span.name == "root").unwrap();
+    assert_eq!(root.properties, vec![
+        ("str".into(), Value::Str("v".into())),
+        ("i64".into(), Value::I64(-1)),
+        ("f64".into(), Value::F64(0.5)),
+        ("bool".into(), Value::Bool(true)),
+    ]);
+    let span = spans.iter().find(|span| span.name == "span").unwrap();
+    assert_eq!(span.properties, vec![
+        ("bytes".into(), Value::Bytes(vec![0, 1].into())),
+        (
+            "array".into(),
+            Value::Array(vec![Value::I64(1), Value::I64(2)])
+        ),
+    ]);
+    assert_eq!(span.events[0].properties, vec![(
+        "u32".into(),
+        Value::I64(42)
+    )]);
+}
+
 #[test]
 #[serial]
 fn test_event() {
@@ -715,6 +777,267 @@ fn test_macro_properties() {
     "###);
 }
 
+#[test]
+#[serial]
+fn test_macro_fields() {
+    #[allow(unused_variables)]
+    #[trace(short_name = true, skip(password), fields(user.len = user.len(), host = %host))]
+    fn login(user: &str, password: &str, host: std::net::Ipv4Addr) {}
+
+    #[allow(unused_variables)]
+    #[trace(short_name = true, skip_all, fields(bar, "bar.debug" = ?bar))]
+    async fn login_async(user: &str, bar: &Bar) {}
+
+    #[allow(unused_variables)]
+    #[trace(short_name = true, skip(b))]
+    fn pattern((a, b): (u32, u32), Wrapper(c): Wrapper) {}
+
+    #[derive(Debug)]
+    struct Bar;
+
+    struct Wrapper(u32);
+
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        login("alice", "secret", std::net::Ipv4Addr::LOCALHOST);
+        pattern((1, 2), Wrapper(3));
+        pollster::block_on(login_async("alice", &Bar));
+    }
+
+    fastrace::flush();
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        login [("user", "\"alice\""), ("user.len", "5"), ("host", "127.0.0.1")]
+        login_async [("bar", "Bar"), ("bar.debug", "Bar")]
+        pattern [("a", "1"), ("c", "3")]
+    "###);
+}
+
+#[test]
+#[serial]
+fn test_macro_ret_err() {
+    #[trace(short_name = true, ret, err)]
+    fn parse(s: &str) -> Result<u64, std::num::ParseIntError> {
+        let n = s.parse()?;
+        if n == 0 {
+            return Ok(1);
+        }
+        Ok(n)
+    }
+
+    #[trace(short_name = true, ret, err)]
+    fn format(s: &str) -> Result<String, std::num::ParseIntError> {
+        Ok(format!("{}", s.parse::<u64>()?))
+    }
+
+    #[trace(short_name = true, ret(Display))]
+    fn double(n: u64) -> u64 {
+        n * 2
+    }
+
+    #[trace(short_name = true, err(Debug))]
+    async fn parse_async(s: &str) -> Result<u64, std::num::ParseIntError> {
+        let n = s.parse()?;
+        Ok(n)
+    }
+
+    #[trace(short_name = true, enter_on_poll = true, ret)]
+    async fn double_async(n: u64) -> u64 {
+        n * 2
+    }
+
+    #[async_trait::async_trait]
+    trait Parser {
+        async fn parse(&self, s: &str) -> Result<u64, String>;
+    }
+
+    struct StrictParser;
+
+    #[async_trait::async_trait]
+    impl Parser for StrictParser {
+        #[trace(name = "StrictParser::parse", ret, err)]
+        async fn parse(&self, s: &str) -> Result<u64, String> {
+            s.parse().map_err(|_| format!("invalid number: {s}"))
+        }
+    }
+
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        parse("0").unwrap();
+        parse("a").unwrap_err();
+        format("b").unwrap_err();
+        double(2);
+        pollster::block_on(async {
+            parse_async("a").await.unwrap_err();
+            double_async(3).await;
+            StrictParser.parse("1").await.unwrap();
+            StrictParser.parse("b").await.unwrap_err();
+        });
+    }
+
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    let graph = tree_str_from_span_records(spans.clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        StrictParser::parse []
+        StrictParser::parse [("return", "1")]
+        double [("return", "4")]
+        double_async [("return", "6")]
+        format []
+        parse []
+        parse [("return", "1")]
+        parse_async []
+    "###);
+
+    let mut errors: Vec<_> = spans
+        .iter()
+        .filter(|span| span.status != Status::Unset)
+        .map(|span| (span.name.to_string(), span.status.clone()))
+        .collect();
+    errors.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(errors, vec![
+        (
+            "StrictParser::parse".to_string(),
+            Status::Error("invalid number: b".into())
+        ),
+        (
+            "format".to_string(),
+            Status::Error("invalid digit found in string".into())
+        ),
+        (
+            "parse".to_string(),
+            Status::Error("invalid digit found in string".into())
+        ),
+        (
+            "parse_async".to_string(),
+            Status::Error("ParseIntError { kind: InvalidDigit }".into())
+        ),
+    ]);
+}
+
+#[test]
+#[serial]
+fn test_macro_level() {
+    #[trace(short_name = true, level = "debug", ret)]
+    fn debug(n: u64) -> u64 {
+        n
+    }
+
+    #[trace(short_name = true, level = "warn")]
+    async fn warn() {}
+
+    #[trace(short_name = true, if = n % 2 == 0)]
+    fn even(n: u64) {}
+
+    #[trace(short_name = true, level = "trace", if = n % 2 == 0)]
+    fn even_trace(n: u64) {}
+
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        debug(1);
+        pollster::block_on(warn());
+        even(1);
+        even(2);
+        even_trace(2);
+
+        fastrace::set_max_level(Level::Trace);
+        debug(2);
+        even_trace(3);
+        even_trace(4);
+        fastrace::set_max_level(Level::Info);
+    }
+
+    fastrace::flush();
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        debug [("return", "2")]
+        even []
+        even_trace []
+        warn []
+    "###);
+}
+
+#[test]
+#[serial]
+fn test_macro_filter() {
+    mod db {
+        use fastrace::prelude::*;
+
+        #[trace(short_name = true)]
+        pub fn query() {}
+
+        #[trace(short_name = true, level = "debug")]
+        pub async fn connect() {}
+    }
+
+    #[trace(short_name = true)]
+    fn handle() {
+        db::query();
+        pollster::block_on(db::connect());
+    }
+
+    #[trace(name = "cache::get", level = "trace")]
+    fn get() {}
+
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        handle();
+        get();
+
+        fastrace::set_filter(
+            format!("{}::db=off,cache=trace", module_path!())
+                .parse()
+                .unwrap(),
+        );
+        handle();
+        get();
+
+        fastrace::set_filter(format!("{}::db=debug", module_path!()).parse().unwrap());
+        handle();
+
+        fastrace::set_filter(Filter::default());
+        handle();
+    }
+
+    fastrace::flush();
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        cache::get []
+        handle []
+        handle []
+            connect []
+            query []
+        handle []
+            query []
+        handle []
+            query []
+    "###);
+}
+
 #[test]
 #[serial]
 fn test_not_sampled() {
@@ -743,3 +1066,406 @@ fn test_not_sampled() {
     fastrace::flush();
     assert!(collected_spans.lock().is_empty());
 }
+
+#[test]
+#[serial]
+fn test_sampler() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+    fastrace::set_sampler(
+        PerNameSampler::new(ParentBasedSampler::new(AlwaysSample)).with_rule("health", NeverSample),
+    );
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        let _span = LocalSpan::enter_with_local_parent("span");
+    }
+    {
+        let root = Span::root("health", SpanContext::random());
+        let _g = root.set_local_parent();
+        let _span = LocalSpan::enter_with_local_parent("span");
+    }
+    {
+        let _root = Span::root("not-sampled", SpanContext::random().sampled(false));
+    }
+    fastrace::flush();
+    fastrace::set_sampler(ParentBasedSampler::new(AlwaysSample));
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        span []
+    "###);
+}
+
+#[test]
+#[serial]
+fn test_rate_limiting_sampler() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+    fastrace::set_sampler(RateLimitingSampler::new(f64::INFINITY).with_name_limit("root", 1.0));
+    for _ in 0..10 {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        let _span = LocalSpan::enter_with_local_parent("span");
+    }
+    fastrace::flush();
+    fastrace::set_sampler(ParentBasedSampler::new(AlwaysSample));
+
+    let graph = tree_str_from_span_records(collected_spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root [("sampling.rate", "1")]
+        span []
+    "###);
+}
+
+#[test]
+#[serial]
+fn test_links() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    let producer1 = SpanContext::new(TraceId(1), SpanId(2));
+    let producer2 = SpanContext::new(TraceId(3), SpanId(4));
+    {
+        let root = Span::root("consume", SpanContext::random())
+            .with_link(producer1, || [("message", "1")]);
+        root.add_link(producer2, || [("message", "2")]);
+    }
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].links, vec![
+        LinkRecord {
+            trace_id: TraceId(1),
+            span_id: SpanId(2),
+            sampled: true,
+            properties: vec![("message".into(), "1".into())],
+        },
+        LinkRecord {
+            trace_id: TraceId(3),
+            span_id: SpanId(4),
+            sampled: true,
+            properties: vec![("message".into(), "2".into())],
+        },
+    ]);
+}
+
+#[test]
+#[serial]
+fn test_status() {
+    #[derive(Debug)]
+    struct Error(&'static str, Option<Box<Error>>);
+
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Error {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.1.as_deref().map(|e| e as _)
+        }
+    }
+
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+    {
+        let root = Span::root("root", SpanContext::random());
+        root.set_status(Status::Ok);
+        let _g = root.set_local_parent();
+        {
+            let _span = LocalSpan::enter_with_local_parent("local");
+            LocalSpan::set_status(Status::Error("local failed".into()));
+        }
+        {
+            let _span = LocalSpan::enter_with_local_parent("chained");
+            LocalSpan::record_error(&Error("outer", Some(Box::new(Error("inner", None)))));
+        }
+        let child = Span::enter_with_parent("child", &root);
+        child.record_error(&Error("child failed", None));
+    }
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    let span = |name: &str| spans.iter().find(|span| span.name == name).unwrap();
+    assert_eq!(span("root").status, Status::Ok);
+    assert_eq!(span("local").status, Status::Error("local failed".into()));
+    assert_eq!(span("chained").status, Status::Error("outer".into()));
+    assert_eq!(span("child").status, Status::Error("child failed".into()));
+
+    let graph = tree_str_from_span_records(spans.clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        chained [] [("exception", [("exception.message", "outer"), ("exception.source.0", "inner")])]
+        child [] [("exception", [("exception.message", "child failed")])]
+        local []
+    "###);
+}
+
+#[test]
+#[serial]
+fn test_kind() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+    {
+        let root = Span::root("root", SpanContext::random()).with_kind(SpanKind::Server);
+        let _g = root.set_local_parent();
+        let _local = LocalSpan::enter_with_local_parent("local").with_kind(SpanKind::Client);
+        let _child = Span::enter_with_parent("child", &root).with_kind(SpanKind::Producer);
+        let _internal = LocalSpan::enter_with_local_parent("internal");
+    }
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    let span = |name: &str| spans.iter().find(|span| span.name == name).unwrap();
+    assert_eq!(span("root").kind, SpanKind::Server);
+    assert_eq!(span("local").kind, SpanKind::Client);
+    assert_eq!(span("child").kind, SpanKind::Producer);
+    assert_eq!(span("internal").kind, SpanKind::Internal);
+}
+
+#[test]
+#[serial]
+fn test_trace_state_and_baggage() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    let trace_state = TraceState::decode_w3c_tracestate("congo=t61rcWkgMzE");
+    let baggage = Baggage::decode_w3c_baggage("user.id=alice");
+    {
+        let parent = SpanContext::decode_w3c_traceparent(
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+        )
+        .unwrap();
+        let parent = TraceContext::new(parent)
+            .with_trace_state(trace_state.clone())
+            .with_baggage(baggage.clone());
+        let root = Span::root_with_context("root", parent);
+        let _g = root.set_local_parent();
+        let _local = LocalSpan::enter_with_local_parent("local");
+
+        let current = TraceContext::current_local_parent().unwrap();
+        assert_eq!(
+            current.span_context.trace_id,
+            SpanContext::current_local_parent().unwrap().trace_id
+        );
+        assert_eq!(current.trace_state, trace_state);
+        assert_eq!(current.baggage.get("user.id"), Some("alice"));
+
+        let child = Span::enter_with_local_parent("child");
+        let context = TraceContext::from_span(&child).unwrap();
+        assert_eq!(context.trace_state, trace_state);
+        assert_eq!(context.baggage, baggage);
+    }
+    {
+        let root = Span::root("other", SpanContext::random());
+        let context = TraceContext::from_span(&root).unwrap();
+        assert!(context.trace_state.is_empty());
+        assert!(context.baggage.is_empty());
+    }
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    assert_eq!(spans.len(), 4);
+    for span in &spans {
+        if span.name == "other" {
+            assert!(span.trace_state.is_empty());
+        } else {
+            assert_eq!(span.trace_state, trace_state);
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_thread_info() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    std::thread::Builder::new()
+        .name("main-worker".to_string())
+        .spawn(|| {
+            let root = Span::root("root", SpanContext::random());
+            let _g = root.set_local_parent();
+            let _local = LocalSpan::enter_with_local_parent("local");
+
+            let child = Span::enter_with_local_parent("child");
+            std::thread::Builder::new()
+                .name("other-worker".to_string())
+                .spawn(move || {
+                    let _g = child.set_local_parent();
+                    let _local = LocalSpan::enter_with_local_parent("other-local");
+                })
+                .unwrap()
+                .join()
+                .unwrap();
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    assert_eq!(spans.len(), 4);
+    let thread_name = |name: &str| {
+        let span = spans.iter().find(|span| span.name == name).unwrap();
+        assert!(span.thread_id.is_some());
+        span.thread_name.as_deref().unwrap().to_string()
+    };
+    assert_eq!(thread_name("root"), "main-worker");
+    assert_eq!(thread_name("local"), "main-worker");
+    assert_eq!(thread_name("child"), "other-worker");
+    assert_eq!(thread_name("other-local"), "other-worker");
+
+    let thread_id = |name: &str| {
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .unwrap()
+            .thread_id
+    };
+    assert_eq!(thread_id("root"), thread_id("local"));
+    assert_ne!(thread_id("root"), thread_id("other-local"));
+}
+
+#[test]
+#[serial]
+fn test_poll_stats() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        pollster::block_on(
+            async {
+                std::thread::sleep(Duration::from_millis(10));
+                futures_timer::Delay::new(Duration::from_millis(20)).await;
+            }
+            .in_span(Span::enter_with_parent("task", &root))
+            .with_poll_stats(),
+        );
+
+        let _dropped = async {}
+            .in_span(Span::enter_with_parent("dropped", &root))
+            .with_poll_stats();
+        pollster::block_on(async {}.in_span(Span::enter_with_parent("untracked", &root)));
+    }
+    fastrace::flush();
+
+    let spans = collected_spans.lock().clone();
+    let property = |name: &str, key: &str| {
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .unwrap()
+            .properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    };
+    let ms = |ns: Option<Value>| match ns {
+        Some(Value::I64(ns)) => ns / 1_000_000,
+        other => panic!("unexpected value: {other:?}"),
+    };
+
+    assert!(ms(property("task", fastrace::future::POLL_BUSY_NS_PROPERTY)) >= 10);
+    assert!(ms(property("task", fastrace::future::POLL_IDLE_NS_PROPERTY)) >= 20);
+    assert!(matches!(
+        property("task", fastrace::future::POLL_COUNT_PROPERTY),
+        Some(Value::I64(count)) if count >= 2
+    ));
+
+    assert_eq!(
+        property("dropped", fastrace::future::POLL_COUNT_PROPERTY),
+        Some(Value::I64(0))
+    );
+    assert_eq!(
+        property("untracked", fastrace::future::POLL_COUNT_PROPERTY),
+        None
+    );
+}
+
+#[test]
+#[serial]
+fn test_stats() {
+    let (reporter, collected_spans) = TestReporter::new();
+    fastrace::set_reporter(reporter, Config::default());
+    fastrace::flush();
+
+    let before = fastrace::collector::stats();
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        let _span = LocalSpan::enter_with_local_parent("span");
+    }
+    {
+        let root = Span::root("canceled", SpanContext::random());
+        root.cancel();
+    }
+    fastrace::flush();
+    let after = fastrace::collector::stats();
+
+    assert_eq!(collected_spans.lock().len(), 2);
+    assert_eq!(after.spans_submitted - before.spans_submitted, 3);
+    assert_eq!(after.canceled_collects - before.canceled_collects, 1);
+    assert_eq!(after.records_reported - before.records_reported, 2);
+    assert!(after.commands_received - before.commands_received >= 7);
+    assert!(after.report_duration.count > before.report_duration.count);
+    assert!(after.handle_commands_duration.count > before.handle_commands_duration.count);
+}
+
+#[test]
+#[serial]
+fn test_shutdown() {
+    struct ShutdownReporter {
+        spans: Arc<Mutex<Vec<SpanRecord>>>,
+        shutdown: Arc<AtomicBool>,
+    }
+
+    impl Reporter for ShutdownReporter {
+        fn report(&mut self, mut spans: Vec<SpanRecord>) {
+            self.spans.lock().append(&mut spans);
+        }
+
+        fn shutdown(&mut self) {
+            self.shutdown.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let shutdown = Arc::new(AtomicBool::new(false));
+    fastrace::set_reporter(
+        ShutdownReporter {
+            spans: spans.clone(),
+            shutdown: shutdown.clone(),
+        },
+        Config::default(),
+    );
+
+    let handle = std::thread::spawn(|| {
+        let root = Span::root("root", SpanContext::random());
+        let _g = root.set_local_parent();
+        let _span = LocalSpan::enter_with_local_parent("span");
+        std::thread::sleep(Duration::from_millis(100));
+    });
+    std::thread::sleep(Duration::from_millis(10));
+
+    fastrace::shutdown(Duration::from_secs(10));
+    assert!(shutdown.load(Ordering::SeqCst));
+    handle.join().unwrap();
+
+    let graph = tree_str_from_span_records(spans.lock().clone());
+    insta::assert_snapshot!(graph, @r###"
+    root []
+        span []
+    "###);
+
+    {
+        let _root = Span::root("ignored", SpanContext::random());
+    }
+    fastrace::flush();
+    assert_eq!(spans.lock().len(), 2);
+}
@@ -0,0 +1,6 @@
+use fastrace::trace;
+
+#[trace(properties = { "a": "{a}" }, fields(a))]
+fn f(a: u32) {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use fastrace::trace;
+
+#[trace(fields(b))]
+fn f(a: u32) {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use fastrace::trace;
+
+#[trace(enter_on_poll = true, fields(a))]
+async fn f(a: u32) {}
+
+fn main() {}
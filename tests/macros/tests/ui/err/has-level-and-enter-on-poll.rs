@@ -0,0 +1,6 @@
+use fastrace::trace;
+
+#[trace(enter_on_poll = true, level = "debug")]
+async fn f() {}
+
+fn main() {}
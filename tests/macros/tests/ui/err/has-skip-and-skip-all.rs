@@ -0,0 +1,6 @@
+use fastrace::trace;
+
+#[trace(skip(a), skip_all)]
+fn f(a: u32) {}
+
+fn main() {}
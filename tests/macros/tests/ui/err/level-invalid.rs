@@ -0,0 +1,6 @@
+use fastrace::trace;
+
+#[trace(level = "verbose")]
+fn f() {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fastrace::trace;
+
+#[trace(ret(Json))]
+fn f() -> u32 {
+    1
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use fastrace::trace;
+
+#[trace(skip(b))]
+fn f(a: u32) {}
+
+fn main() {}
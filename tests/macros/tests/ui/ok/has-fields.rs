@@ -0,0 +1,20 @@
+use fastrace::trace;
+
+#[trace(short_name = true, skip(c), fields(a, b.len = b.len(), d = %a, e = ?b))]
+async fn f(a: i64, b: &str, c: Bar) -> i64 {
+    drop(c);
+    a
+}
+
+struct Bar;
+
+#[trace(skip_all, fields("k" = 1))]
+fn g(a: u32, _b: Bar) -> u32 {
+    a
+}
+
+#[tokio::main]
+async fn main() {
+    f(1, "b", Bar).await;
+    g(1, Bar);
+}
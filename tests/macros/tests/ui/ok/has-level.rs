@@ -0,0 +1,22 @@
+use fastrace::trace;
+
+#[trace(level = "debug")]
+fn f(a: u32) -> u32 {
+    a
+}
+
+#[trace(short_name = true, level = "TRACE", if = a > 1, ret, err)]
+async fn g(a: u32) -> Result<u32, String> {
+    Ok(a)
+}
+
+#[trace(if = !s.is_empty())]
+fn h(s: &str) {}
+
+#[tokio::main]
+async fn main() {
+    fastrace::set_max_level(fastrace::Level::Debug);
+    f(1);
+    g(2).await.unwrap();
+    h("");
+}
@@ -0,0 +1,64 @@
+use fastrace::trace;
+
+struct Buffer {
+    data: Vec<u8>,
+}
+
+impl Buffer {
+    #[trace(ret)]
+    fn get(&mut self) -> &mut Vec<u8> {
+        &mut self.data
+    }
+
+    #[trace(ret)]
+    fn first(&self) -> Option<&u8> {
+        let first = self.data.first()?;
+        Some(first)
+    }
+
+    #[trace(ret, err)]
+    fn get_or_err(&mut self) -> Result<&mut Vec<u8>, String> {
+        if self.data.is_empty() {
+            return Err("empty".to_string());
+        }
+        Ok(&mut self.data)
+    }
+
+    #[trace(ret)]
+    async fn get_async(&mut self) -> &mut Vec<u8> {
+        &mut self.data
+    }
+}
+
+#[trace(ret)]
+fn nested(a: u32) -> u32 {
+    fn inner(a: u32) -> u32 {
+        return a + 1;
+    }
+    let double = |a: u32| -> u32 { return a * 2 };
+    for i in 0.. {
+        if i == a {
+            return inner(double(i));
+        }
+    }
+    0
+}
+
+#[trace(ret)]
+fn unit(a: u32) {
+    if a == 0 {
+        return;
+    }
+    println!("{a}");
+}
+
+#[tokio::main]
+async fn main() {
+    let mut buffer = Buffer { data: vec![1] };
+    buffer.get().push(2);
+    let _ = buffer.first();
+    let _ = buffer.get_or_err();
+    buffer.get_async().await.push(3);
+    nested(1);
+    unit(1);
+}
@@ -0,0 +1,58 @@
+use fastrace::trace;
+
+#[trace(ret, err)]
+fn f(a: &str) -> Result<u32, std::num::ParseIntError> {
+    let a = a.parse()?;
+    if a == 0 {
+        return Ok(1);
+    }
+    Ok(a)
+}
+
+#[trace(ret(Display))]
+fn g(a: u32) -> impl std::fmt::Display + std::fmt::Debug {
+    a
+}
+
+#[trace(err(Debug))]
+async fn h(a: &str) -> Result<(), String> {
+    if a.is_empty() {
+        return Err("empty".to_string());
+    }
+    Ok(())
+}
+
+#[trace(err)]
+fn i() -> Result<(), String> {
+    todo!()
+}
+
+#[trace(enter_on_poll = true, ret)]
+async fn j(a: u32) -> u32 {
+    a
+}
+
+#[async_trait::async_trait]
+trait MyTrait {
+    async fn work(&self, a: &str) -> Result<u32, String>;
+}
+
+struct MyStruct;
+
+#[async_trait::async_trait]
+impl MyTrait for MyStruct {
+    #[trace(ret, err)]
+    async fn work(&self, a: &str) -> Result<u32, String> {
+        a.parse().map_err(|_| a.to_string())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = f("1");
+    g(1);
+    let _ = h("").await;
+    let _ = i;
+    j(1).await;
+    let _ = MyStruct.work("1").await;
+}
@@ -0,0 +1,42 @@
+use std::task::Poll;
+
+use fastrace::trace;
+
+type Maybe<T> = Option<T>;
+
+#[trace(ret)]
+fn poll(a: &str) -> Poll<Result<u32, std::num::ParseIntError>> {
+    let a = a.parse::<u32>()?;
+    Poll::Ready(Ok(a))
+}
+
+#[trace(ret)]
+fn alias(a: Option<u32>) -> Maybe<u32> {
+    let a = a?;
+    Some(a + 1)
+}
+
+#[trace(ret)]
+async fn alias_async(a: Option<u32>) -> Maybe<u32> {
+    let a = a?;
+    Some(a + 1)
+}
+
+#[trace(ret, err)]
+fn in_macro(a: &str) -> Result<String, std::num::ParseIntError> {
+    Ok(format!("{}", a.parse::<u32>()?))
+}
+
+#[trace(ret, err)]
+async fn in_macro_async(a: &str) -> Result<String, std::num::ParseIntError> {
+    Ok(format!("{}", a.parse::<u32>()?))
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = poll("1");
+    alias(Some(1));
+    alias_async(None).await;
+    let _ = in_macro("a");
+    let _ = in_macro_async("1").await;
+}